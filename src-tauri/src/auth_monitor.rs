@@ -0,0 +1,199 @@
+use crate::openclaw_agents::{self, AgentAuthProfileSummary};
+use crate::util::{now_millis, string_field};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::Emitter;
+
+pub const AUTH_EXPIRING_EVENT: &str = "auth-expiring";
+pub const AUTH_EXPIRED_EVENT: &str = "auth-expired";
+
+const SCAN_INTERVAL: Duration = Duration::from_secs(60);
+// Tokens closer than this to their expiry are reported as expiring.
+const EXPIRING_WINDOW_MS: i64 = 30 * 60 * 1000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum AuthExpiryState {
+    Valid,
+    Expiring,
+    Expired,
+    // The access token is expiring or expired, but a refresh token is stored
+    // and OpenClaw renews it on next use; nothing for the user to do.
+    RefreshPending,
+    // API keys and tokens without an exp claim.
+    NoExpiry,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthProfileHealth {
+    pub agent_id: String,
+    #[serde(flatten)]
+    pub profile: AgentAuthProfileSummary,
+    pub expires_in_ms: Option<i64>,
+    pub state: AuthExpiryState,
+    // A refresh token is stored, so OpenClaw can renew the access token itself.
+    pub refreshable: bool,
+}
+
+// The stored `expires` wins; otherwise the exp claim of an inline JWT.
+fn expiry_millis(credential: &Value) -> Option<i64> {
+    if let Some(expires) = credential.get("expires").and_then(Value::as_i64) {
+        return Some(expires);
+    }
+    ["access", "token"]
+        .iter()
+        .filter_map(|field| string_field(credential, field))
+        .find_map(|token| crate::jwt_exp_millis(&token))
+}
+
+fn profile_health(
+    agent_id: &str,
+    profile_id: &str,
+    credential: &Value,
+    now_ms: i64,
+) -> AuthProfileHealth {
+    let mut profile = openclaw_agents::summarize_profile(profile_id, credential);
+    profile.expires_at_ms = expiry_millis(credential);
+    let expires_in_ms = profile.expires_at_ms.map(|expires| expires - now_ms);
+    // OpenClaw only reads the refresh token inline from auth-profiles.json.
+    let refreshable = string_field(credential, "refresh").is_some();
+    let state = match expires_in_ms {
+        None => AuthExpiryState::NoExpiry,
+        Some(remaining) if remaining <= EXPIRING_WINDOW_MS && refreshable => {
+            AuthExpiryState::RefreshPending
+        }
+        Some(remaining) if remaining <= 0 => AuthExpiryState::Expired,
+        Some(remaining) if remaining <= EXPIRING_WINDOW_MS => AuthExpiryState::Expiring,
+        Some(_) => AuthExpiryState::Valid,
+    };
+    AuthProfileHealth {
+        agent_id: agent_id.to_string(),
+        profile,
+        expires_in_ms,
+        state,
+        refreshable,
+    }
+}
+
+fn scan_file(agent_id: &str, auth_profiles_path: &Path, now_ms: i64) -> Vec<AuthProfileHealth> {
+    let Some(parsed) = fs::read_to_string(auth_profiles_path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<Value>(&raw).ok())
+    else {
+        return Vec::new();
+    };
+    let Some(profiles) = parsed.get("profiles").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    profiles
+        .iter()
+        .map(|(profile_id, credential)| profile_health(agent_id, profile_id, credential, now_ms))
+        .collect()
+}
+
+// Health of every credential across all agents of the active state dir.
+pub fn scan() -> Vec<AuthProfileHealth> {
+    let now_ms = now_millis();
+    crate::list_openclaw_agent_dirs()
+        .into_iter()
+        .flat_map(|(agent_id, agent_dir)| {
+            scan_file(
+                &agent_id,
+                &agent_dir.join(openclaw_agents::AUTH_PROFILES_FILE),
+                now_ms,
+            )
+        })
+        .collect()
+}
+
+// Last seen state per state dir, then per `agent/profile`, so switching
+// profiles neither mixes up nor forgets the transitions of each one.
+type LastStates = BTreeMap<PathBuf, BTreeMap<String, AuthExpiryState>>;
+
+fn last_states() -> &'static Mutex<LastStates> {
+    static STATES: OnceLock<Mutex<LastStates>> = OnceLock::new();
+    STATES.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+// Emits an event only when a profile enters the expiring or expired state, so
+// a token that stays expired is reported once.
+fn scan_and_notify(app: &tauri::AppHandle) {
+    let Ok(mut states) = last_states().lock() else {
+        return;
+    };
+    let last = states
+        .entry(crate::resolve_openclaw_state_dir())
+        .or_default();
+    let mut seen = BTreeMap::new();
+    for health in scan() {
+        let key = format!("{}/{}", health.agent_id, health.profile.id);
+        let changed = last.get(&key) != Some(&health.state);
+        seen.insert(key, health.state);
+        if !changed {
+            continue;
+        }
+        let event = match health.state {
+            AuthExpiryState::Expiring => AUTH_EXPIRING_EVENT,
+            AuthExpiryState::Expired => AUTH_EXPIRED_EVENT,
+            _ => continue,
+        };
+        let _ = app.emit(event, health);
+    }
+    *last = seen;
+}
+
+pub fn start(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        scan_and_notify(&app);
+        std::thread::sleep(SCAN_INTERVAL);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const NOW: i64 = 1_700_000_000_000;
+
+    fn state_of(credential: Value) -> AuthExpiryState {
+        profile_health("main", "p", &credential, NOW).state
+    }
+
+    #[test]
+    fn refreshable_oauth_is_not_reported_as_expired() {
+        let expired = json!({ "type": "oauth", "access": "a", "refresh": "r", "expires": NOW - 1 });
+        assert_eq!(state_of(expired), AuthExpiryState::RefreshPending);
+        let expiring =
+            json!({ "type": "oauth", "access": "a", "refresh": "r", "expires": NOW + 60_000 });
+        assert_eq!(state_of(expiring), AuthExpiryState::RefreshPending);
+    }
+
+    #[test]
+    fn credentials_without_refresh_token_expire() {
+        assert_eq!(
+            state_of(json!({ "type": "token", "token": "t", "expires": NOW - 1 })),
+            AuthExpiryState::Expired
+        );
+        assert_eq!(
+            state_of(json!({ "type": "oauth", "access": "a", "expires": NOW + 60_000 })),
+            AuthExpiryState::Expiring
+        );
+        assert_eq!(
+            state_of(
+                json!({ "type": "oauth", "access": "a", "refresh": "r", "expires": NOW + EXPIRING_WINDOW_MS + 1 })
+            ),
+            AuthExpiryState::Valid
+        );
+        assert_eq!(
+            state_of(json!({ "type": "api_key", "key": "k" })),
+            AuthExpiryState::NoExpiry
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use crate::openclaw_agents::{self, AgentAuthProfileSummary};
+use crate::openclaw_config::OpenClawConfig;
+use crate::util::string_field;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+// Credential fields that carry secrets in auth-profiles.json.
+pub const SECRET_FIELDS: &[&str] = &["key", "token", "access", "refresh"];
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthProfileEntry {
+    // Provider and email fall back to the config for profiles only it lists.
+    #[serde(flatten)]
+    pub profile: AgentAuthProfileSummary,
+    pub agent_id: String,
+    // `auth.profiles.<id>.mode` from the config.
+    pub mode: Option<String>,
+    // Names of the secret fields the credential holds, never their values.
+    pub secret_fields: Vec<String>,
+    pub in_auth_profiles: bool,
+    pub in_config: bool,
+    // Position in the effective order; 0 is the primary profile.
+    pub order_index: Option<usize>,
+    pub primary: bool,
+}
+
+// Profile ids are `<provider>:<name>` keys; keep them free of whitespace.
+pub fn is_valid_profile_id(id: &str) -> bool {
+    !id.is_empty() && !id.chars().any(|ch| ch.is_whitespace() || ch.is_control())
+}
+
+// A bare name keeps the `<provider>:` prefix; a full id must carry it.
+pub fn renamed_profile_id(provider: &str, new_id: &str) -> Result<String, String> {
+    let new_id = match new_id.trim() {
+        name if name.contains(':') => name.to_string(),
+        name => format!("{}:{}", provider, name),
+    };
+    if new_id.split_once(':').map(|(prefix, _)| prefix) != Some(provider) {
+        return Err(format!(
+            "Auth profile ids of {} must start with '{}:'.",
+            provider, provider
+        ));
+    }
+    if !is_valid_profile_id(&new_id) || new_id.ends_with(':') {
+        return Err(format!("'{}' is not a valid auth profile id.", new_id));
+    }
+    Ok(new_id)
+}
+
+pub fn profiles(auth_profiles: &Value) -> Option<&Map<String, Value>> {
+    auth_profiles.get("profiles").and_then(Value::as_object)
+}
+
+pub fn profiles_mut(auth_profiles: &mut Value) -> Option<&mut Map<String, Value>> {
+    auth_profiles
+        .get_mut("profiles")
+        .and_then(Value::as_object_mut)
+}
+
+// Points every per-profile entry besides the credential itself (`order`,
+// `lastGood`, `usageStats`) at `new_id`, or drops it when `new_id` is None.
+fn retarget_profile_refs(auth_profiles: &mut Value, profile_id: &str, new_id: Option<&str>) {
+    let Some(root) = auth_profiles.as_object_mut() else {
+        return;
+    };
+    if let Some(order) = root.get_mut("order").and_then(Value::as_object_mut) {
+        for ids in order.values_mut().filter_map(Value::as_array_mut) {
+            match new_id {
+                Some(new_id) => ids
+                    .iter_mut()
+                    .filter(|id| id.as_str() == Some(profile_id))
+                    .for_each(|id| *id = Value::from(new_id)),
+                None => ids.retain(|id| id.as_str() != Some(profile_id)),
+            }
+        }
+    }
+    if let Some(last_good) = root.get_mut("lastGood").and_then(Value::as_object_mut) {
+        match new_id {
+            Some(new_id) => last_good
+                .values_mut()
+                .filter(|id| id.as_str() == Some(profile_id))
+                .for_each(|id| *id = Value::from(new_id)),
+            None => last_good.retain(|_, id| id.as_str() != Some(profile_id)),
+        }
+    }
+    if let Some(usage) = root.get_mut("usageStats").and_then(Value::as_object_mut) {
+        if let Some(stats) = usage.remove(profile_id) {
+            if let Some(new_id) = new_id {
+                usage.insert(new_id.to_string(), stats);
+            }
+        }
+    }
+}
+
+// Removes the profile and everything that refers to it; returns its credential.
+pub fn remove_profile(auth_profiles: &mut Value, profile_id: &str) -> Option<Value> {
+    let removed = profiles_mut(auth_profiles).and_then(|profiles| profiles.remove(profile_id));
+    retarget_profile_refs(auth_profiles, profile_id, None);
+    removed
+}
+
+pub fn rename_profile(auth_profiles: &mut Value, profile_id: &str, new_id: &str) {
+    let Some(profiles) = profiles_mut(auth_profiles) else {
+        return;
+    };
+    if let Some(credential) = profiles.remove(profile_id) {
+        profiles.insert(new_id.to_string(), credential);
+        retarget_profile_refs(auth_profiles, profile_id, Some(new_id));
+    }
+}
+
+// The store's own `order.<provider>`, if it has one.
+pub fn store_order(auth_profiles: &Value, provider: &str) -> Option<Vec<String>> {
+    let ids = auth_profiles.get("order")?.get(provider)?.as_array()?;
+    Some(
+        ids.iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+// Replaces `order.<provider>` where the store has one; a store without it
+// keeps following the config.
+pub fn set_store_order(auth_profiles: &mut Value, provider: &str, order: Vec<String>) {
+    if let Some(ids) = auth_profiles
+        .get_mut("order")
+        .and_then(|order| order.get_mut(provider))
+    {
+        *ids = Value::from(order);
+    }
+}
+
+// OpenClaw tries an agent's profiles in the store's `order.<provider>` when
+// it has one, and in the config's `auth.order.<provider>` otherwise.
+pub fn effective_order(
+    auth_profiles: &Value,
+    config: &OpenClawConfig,
+    provider: &str,
+) -> Vec<String> {
+    store_order(auth_profiles, provider).unwrap_or_else(|| config.auth_order(provider).to_vec())
+}
+
+// The stored profile of the provider OpenClaw tries first.
+pub fn first_in_order(
+    auth_profiles: &Value,
+    config: &OpenClawConfig,
+    provider: &str,
+) -> Option<String> {
+    let order = effective_order(auth_profiles, config, provider);
+    profiles(auth_profiles)?
+        .iter()
+        .filter(|(_, credential)| string_field(credential, "provider").as_deref() == Some(provider))
+        .min_by_key(|(id, _)| {
+            order
+                .iter()
+                .position(|entry| entry == *id)
+                .unwrap_or(usize::MAX)
+        })
+        .map(|(id, _)| id.clone())
+}
+
+fn secret_fields(credential: &Value) -> Vec<String> {
+    SECRET_FIELDS
+        .iter()
+        .filter(|field| string_field(credential, field).is_some())
+        .map(|field| field.to_string())
+        .collect()
+}
+
+// Every profile of one agent's auth-profiles.json, plus the ones only the
+// config still mentions, with their place in `auth.order`.
+pub fn list(
+    agent_id: &str,
+    auth_profiles: &Value,
+    config: &OpenClawConfig,
+) -> Vec<AuthProfileEntry> {
+    let empty = Map::new();
+    let stored = profiles(auth_profiles).unwrap_or(&empty);
+    let configured: BTreeSet<&String> = config
+        .auth
+        .as_ref()
+        .and_then(|auth| auth.profiles.as_ref())
+        .map(|profiles| profiles.keys().collect())
+        .unwrap_or_default();
+    let ids: BTreeSet<&String> = stored.keys().chain(configured.iter().copied()).collect();
+
+    ids.into_iter()
+        .map(|id| {
+            let credential = stored.get(id);
+            let meta = config.auth_profile(id);
+            let mut profile = match credential {
+                Some(credential) => openclaw_agents::summarize_profile(id, credential),
+                None => AgentAuthProfileSummary {
+                    id: id.clone(),
+                    provider: None,
+                    kind: None,
+                    email: None,
+                    expires_at_ms: None,
+                },
+            };
+            if let Some(meta) = meta {
+                profile.provider = profile.provider.or_else(|| meta.provider.clone());
+                profile.email = profile.email.or_else(|| meta.email.clone());
+            }
+            let order_index = profile.provider.as_deref().and_then(|provider| {
+                effective_order(auth_profiles, config, provider)
+                    .iter()
+                    .position(|entry| entry == id)
+            });
+            AuthProfileEntry {
+                profile,
+                agent_id: agent_id.to_string(),
+                mode: meta.and_then(|meta| meta.mode.clone()),
+                secret_fields: credential.map(secret_fields).unwrap_or_default(),
+                in_auth_profiles: credential.is_some(),
+                in_config: meta.is_some(),
+                order_index,
+                primary: order_index == Some(0),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store() -> Value {
+        json!({
+            "version": 1,
+            "profiles": {
+                "anthropic:work": { "type": "api_key", "provider": "anthropic", "key": "k1" },
+                "anthropic:home": { "type": "api_key", "provider": "anthropic", "key": "k2" }
+            },
+            "order": { "anthropic": ["anthropic:work", "anthropic:home"] },
+            "lastGood": { "anthropic": "anthropic:work", "openai": "openai:default" },
+            "usageStats": {
+                "anthropic:work": { "lastUsed": 1 },
+                "anthropic:home": { "lastUsed": 2 }
+            }
+        })
+    }
+
+    #[test]
+    fn remove_profile_drops_every_reference() {
+        let mut value = store();
+        let removed = remove_profile(&mut value, "anthropic:work").unwrap();
+        assert_eq!(removed["key"], "k1");
+        assert_eq!(
+            value,
+            json!({
+                "version": 1,
+                "profiles": {
+                    "anthropic:home": { "type": "api_key", "provider": "anthropic", "key": "k2" }
+                },
+                "order": { "anthropic": ["anthropic:home"] },
+                "lastGood": { "openai": "openai:default" },
+                "usageStats": { "anthropic:home": { "lastUsed": 2 } }
+            })
+        );
+    }
+
+    #[test]
+    fn rename_profile_moves_every_reference() {
+        let mut value = store();
+        rename_profile(&mut value, "anthropic:work", "anthropic:office");
+        assert!(value["profiles"].get("anthropic:work").is_none());
+        assert_eq!(value["profiles"]["anthropic:office"]["key"], "k1");
+        assert_eq!(
+            value["order"]["anthropic"],
+            json!(["anthropic:office", "anthropic:home"])
+        );
+        assert_eq!(value["lastGood"]["anthropic"], "anthropic:office");
+        assert_eq!(
+            value["usageStats"]["anthropic:office"],
+            json!({ "lastUsed": 1 })
+        );
+        assert!(value["usageStats"].get("anthropic:work").is_none());
+    }
+
+    #[test]
+    fn store_order_wins_over_the_config() {
+        let config = OpenClawConfig::from_value(json!({
+            "auth": { "order": { "anthropic": ["anthropic:home", "anthropic:work"] } }
+        }))
+        .unwrap();
+        let entries = list("main", &store(), &config);
+        let work = entries
+            .iter()
+            .find(|e| e.profile.id == "anthropic:work")
+            .unwrap();
+        assert_eq!(work.order_index, Some(0));
+        assert!(work.primary);
+
+        let mut without_order = store();
+        without_order.as_object_mut().unwrap().remove("order");
+        let entries = list("main", &without_order, &config);
+        let home = entries
+            .iter()
+            .find(|e| e.profile.id == "anthropic:home")
+            .unwrap();
+        assert!(home.primary);
+    }
+
+    #[test]
+    fn first_in_order_follows_the_store_order() {
+        let config = OpenClawConfig::from_value(json!({
+            "auth": { "order": { "anthropic": ["anthropic:home", "anthropic:work"] } }
+        }))
+        .unwrap();
+        assert_eq!(
+            first_in_order(&store(), &config, "anthropic").as_deref(),
+            Some("anthropic:work")
+        );
+
+        let mut value = store();
+        value["order"]["anthropic"] = json!(["anthropic:missing", "anthropic:home"]);
+        assert_eq!(
+            first_in_order(&value, &config, "anthropic").as_deref(),
+            Some("anthropic:home")
+        );
+        assert_eq!(first_in_order(&value, &config, "openai"), None);
+    }
+
+    #[test]
+    fn set_store_order_only_touches_existing_orders() {
+        let mut value = store();
+        let order = vec!["anthropic:home".to_string(), "anthropic:work".to_string()];
+        set_store_order(&mut value, "anthropic", order.clone());
+        assert_eq!(store_order(&value, "anthropic"), Some(order));
+
+        set_store_order(&mut value, "openai", vec!["openai:default".to_string()]);
+        assert_eq!(store_order(&value, "openai"), None);
+    }
+
+    #[test]
+    fn renamed_ids_keep_the_provider_prefix() {
+        assert_eq!(
+            renamed_profile_id("anthropic", " office ").unwrap(),
+            "anthropic:office"
+        );
+        assert_eq!(
+            renamed_profile_id("anthropic", "anthropic:office").unwrap(),
+            "anthropic:office"
+        );
+        assert!(renamed_profile_id("anthropic", "openai:office").is_err());
+        assert!(renamed_profile_id("anthropic", "anthropic:").is_err());
+        assert!(renamed_profile_id("anthropic", "my office").is_err());
+    }
+
+    #[test]
+    fn rename_of_an_unknown_profile_changes_nothing() {
+        let mut value = store();
+        rename_profile(&mut value, "anthropic:missing", "anthropic:other");
+        assert_eq!(value, store());
+    }
+}
@@ -0,0 +1,480 @@
+use crate::local_credentials::LocalCredentialSource;
+use crate::openclaw_agents::AUTH_PROFILES_FILE;
+use crate::util::{now_millis, string_field};
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::Emitter;
+
+pub const CODEX_SYNC_EVENT: &str = "codex-sync";
+const SETTINGS_FILE: &str = "desktop-codex-sync.json";
+const AUDIT_FILE: &str = "codex-sync-audit.jsonl";
+const AUDIT_MAX_BYTES: u64 = 1024 * 1024;
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(400);
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSyncSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    // Same meaning as the agent_id of reuse_local_codex_auth.
+    #[serde(default)]
+    pub agent_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSyncAuditEntry {
+    pub at_ms: i64,
+    // "startup", "profile-switch", "enable", "file-change" (Codex auth.json)
+    // or "openclaw-change" (an agent's auth-profiles.json).
+    pub trigger: String,
+    // "synced", "skipped" or "failed".
+    pub outcome: String,
+    pub profile_id: Option<String>,
+    pub agent_ids: Vec<String>,
+    // Agents that already hold the newest token.
+    pub skipped_agent_ids: Vec<String>,
+    // Agent whose token OpenClaw refreshed after Codex did, and which was
+    // written back to Codex's auth.json.
+    pub written_back_from: Option<String>,
+    pub last_refresh: Option<String>,
+    pub codex_expires_at_ms: Option<i64>,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSyncStatus {
+    pub enabled: bool,
+    pub agent_id: Option<String>,
+    pub watching: bool,
+    pub codex_auth_path: String,
+    pub audit_path: String,
+    pub last_entry: Option<CodexSyncAuditEntry>,
+}
+
+fn settings_path(state_dir: &Path) -> PathBuf {
+    state_dir.join(SETTINGS_FILE)
+}
+
+pub fn audit_path(state_dir: &Path) -> PathBuf {
+    state_dir.join("logs").join(AUDIT_FILE)
+}
+
+pub fn load_settings(state_dir: &Path) -> CodexSyncSettings {
+    fs::read_to_string(settings_path(state_dir))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_settings(state_dir: &Path, settings: &CodexSyncSettings) -> Result<(), String> {
+    fs::create_dir_all(state_dir).map_err(|err| {
+        format!(
+            "Failed to create state dir {}: {}",
+            state_dir.to_string_lossy(),
+            err
+        )
+    })?;
+    crate::config_txn::write_atomic(
+        &settings_path(state_dir),
+        &serde_json::to_string_pretty(settings)
+            .map_err(|err| format!("Failed to serialize Codex sync settings: {}", err))?,
+        false,
+    )
+}
+
+// Codex rewrites auth.json for unrelated reasons too; only a new
+// `last_refresh` or access token is worth a sync. The token is hashed so it
+// is not kept around in memory.
+fn codex_fingerprint(codex_auth_path: &Path) -> Option<(u64, Option<String>)> {
+    let raw = fs::read_to_string(codex_auth_path).ok()?;
+    let parsed = serde_json::from_str::<Value>(&raw).ok()?;
+    let last_refresh = parsed
+        .get("last_refresh")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let access_token = parsed
+        .pointer("/tokens/access_token")
+        .and_then(Value::as_str);
+    let mut hasher = DefaultHasher::new();
+    last_refresh.hash(&mut hasher);
+    access_token.hash(&mut hasher);
+    Some((hasher.finish(), last_refresh))
+}
+
+fn stored_credential(auth_profiles_path: &Path, profile_id: &str) -> Option<Value> {
+    let raw = fs::read_to_string(auth_profiles_path).ok()?;
+    let parsed = serde_json::from_str::<Value>(&raw).ok()?;
+    parsed.get("profiles")?.get(profile_id).cloned()
+}
+
+fn expires_of(credential: &Value) -> Option<i64> {
+    credential.get("expires").and_then(Value::as_i64)
+}
+
+// `last_refresh` is an RFC 3339 UTC timestamp, as Codex writes it.
+fn rfc3339_utc(ms: i64) -> String {
+    let secs = ms.div_euclid(1000);
+    let (days, day_secs) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        day_secs / 3600,
+        day_secs % 3600 / 60,
+        day_secs % 60,
+        ms.rem_euclid(1000)
+    )
+}
+
+// Puts a token OpenClaw refreshed into Codex's auth.json. The id_token is
+// left as is: OpenClaw does not keep one, and Codex only reads account
+// details from it.
+fn write_back_to_codex(codex_auth_path: &Path, credential: &Value) -> Result<(), String> {
+    let (Some(access), Some(refresh)) = (
+        string_field(credential, "access"),
+        string_field(credential, "refresh"),
+    ) else {
+        return Err("The OpenClaw credential has no access or refresh token.".to_string());
+    };
+    let raw = fs::read_to_string(codex_auth_path).map_err(|err| {
+        format!(
+            "Failed to read {}: {}",
+            codex_auth_path.to_string_lossy(),
+            err
+        )
+    })?;
+    let mut auth = serde_json::from_str::<Value>(&raw)
+        .map_err(|err| format!("Invalid Codex auth file format: {}", err))?;
+    let root = auth
+        .as_object_mut()
+        .ok_or_else(|| "Codex auth file is not a JSON object.".to_string())?;
+    let tokens = root
+        .entry("tokens".to_string())
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| "Codex auth tokens field is not an object.".to_string())?;
+    tokens.insert("access_token".to_string(), serde_json::json!(access));
+    tokens.insert("refresh_token".to_string(), serde_json::json!(refresh));
+    if let Some(account_id) = string_field(credential, "accountId") {
+        tokens.insert("account_id".to_string(), serde_json::json!(account_id));
+    }
+    root.insert(
+        "last_refresh".to_string(),
+        serde_json::json!(rfc3339_utc(now_millis())),
+    );
+    let content = serde_json::to_string_pretty(&auth)
+        .map_err(|err| format!("Failed to serialize Codex auth file: {}", err))?;
+    crate::config_txn::write_atomic(codex_auth_path, &content, true)
+}
+
+fn append_audit(state_dir: &Path, entry: &CodexSyncAuditEntry) {
+    let path = audit_path(state_dir);
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if fs::metadata(&path)
+        .map(|meta| meta.len() >= AUDIT_MAX_BYTES)
+        .unwrap_or(false)
+    {
+        let _ = fs::rename(&path, path.with_extension("jsonl.1"));
+    }
+    let Ok(line) = serde_json::to_string(entry) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+// Most recent entries last.
+pub fn read_audit(state_dir: &Path, limit: usize) -> Vec<CodexSyncAuditEntry> {
+    let Ok(raw) = fs::read_to_string(audit_path(state_dir)) else {
+        return Vec::new();
+    };
+    let entries: Vec<CodexSyncAuditEntry> = raw
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let skip = entries.len().saturating_sub(limit);
+    entries.into_iter().skip(skip).collect()
+}
+
+// Keeps the Codex login and OpenClaw's copies of it on the newest token.
+// Codex's token goes to every agent that does not hold it yet, unless an
+// agent stores a token OpenClaw refreshed after Codex did: that one is
+// written back to Codex and handed to the other agents instead. Watcher
+// triggers that find everything in sync return None and leave no audit entry,
+// so the sync's own writes do not echo.
+pub fn sync_once(
+    state_dir: &Path,
+    agent_id: Option<&str>,
+    trigger: &str,
+) -> Option<CodexSyncAuditEntry> {
+    let codex_auth_path = crate::resolve_codex_auth_path();
+    let last_refresh =
+        codex_fingerprint(&codex_auth_path).and_then(|(_, last_refresh)| last_refresh);
+    let mut entry = CodexSyncAuditEntry {
+        at_ms: now_millis(),
+        trigger: trigger.to_string(),
+        outcome: "failed".to_string(),
+        profile_id: None,
+        agent_ids: Vec::new(),
+        skipped_agent_ids: Vec::new(),
+        written_back_from: None,
+        last_refresh,
+        codex_expires_at_ms: None,
+        message: String::new(),
+    };
+
+    let prepared = crate::local_credentials::CodexSource
+        .load()
+        .and_then(|codex| {
+            crate::resolve_agent_auth_targets(agent_id).map(|targets| (codex, targets))
+        });
+    let (mut codex, targets) = match prepared {
+        Ok(prepared) => prepared,
+        Err(error) => {
+            entry.message = error;
+            append_audit(state_dir, &entry);
+            return Some(entry);
+        }
+    };
+    entry.profile_id = Some(codex.profile_id.clone());
+    entry.codex_expires_at_ms = Some(codex.expires);
+
+    let stored: Vec<_> = targets
+        .into_iter()
+        .map(|target| {
+            let credential = stored_credential(&target.1, &codex.profile_id);
+            (target, credential)
+        })
+        .collect();
+    let codex_access = codex.credential.get("access").cloned();
+    let newer = stored
+        .iter()
+        .filter_map(|((id, _), credential)| Some((id, credential.as_ref()?)))
+        .filter(|(_, credential)| credential.get("access") != codex_access.as_ref())
+        .filter(|(_, credential)| expires_of(credential).is_some_and(|e| e > codex.expires))
+        .max_by_key(|(_, credential)| expires_of(credential));
+
+    let mut messages = Vec::new();
+    if let Some((newer_agent_id, newer_credential)) = newer {
+        if let Err(error) = write_back_to_codex(&codex_auth_path, newer_credential) {
+            entry.message = format!("Failed to update the Codex login: {}", error);
+            append_audit(state_dir, &entry);
+            return Some(entry);
+        }
+        if let Some(credential) = newer_credential.as_object() {
+            codex.credential = credential.clone();
+        }
+        codex.expires = expires_of(newer_credential).unwrap_or(codex.expires);
+        entry.written_back_from = Some(newer_agent_id.clone());
+        entry.codex_expires_at_ms = Some(codex.expires);
+        messages.push(format!(
+            "Wrote the newer token of agent {} back to Codex.",
+            newer_agent_id
+        ));
+    }
+
+    let access = codex.credential.get("access").cloned();
+    let (in_sync, targets): (Vec<_>, Vec<_>) = stored.into_iter().partition(|(_, credential)| {
+        credential.as_ref().and_then(|c| c.get("access")) == access.as_ref()
+    });
+    entry.skipped_agent_ids = in_sync.into_iter().map(|((id, _), _)| id).collect();
+    let targets: Vec<_> = targets.into_iter().map(|(target, _)| target).collect();
+
+    if !targets.is_empty() {
+        match crate::apply_local_oauth_credential(codex, targets, None) {
+            Ok(result) => {
+                entry.agent_ids = result.agent_ids;
+                messages.push(result.message);
+            }
+            Err(error) => {
+                messages.push(error);
+                entry.message = messages.join(" ");
+                append_audit(state_dir, &entry);
+                return Some(entry);
+            }
+        }
+    }
+
+    if messages.is_empty() {
+        if matches!(trigger, "file-change" | "openclaw-change") {
+            return None;
+        }
+        entry.outcome = "skipped".to_string();
+        entry.message = "Codex and OpenClaw already hold the same token.".to_string();
+    } else {
+        entry.outcome = "synced".to_string();
+        entry.message = messages.join(" ");
+    }
+    append_audit(state_dir, &entry);
+    Some(entry)
+}
+
+fn watcher_slot() -> &'static Mutex<Option<Debouncer<RecommendedWatcher>>> {
+    static SLOT: OnceLock<Mutex<Option<Debouncer<RecommendedWatcher>>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+pub fn is_watching() -> bool {
+    watcher_slot()
+        .lock()
+        .map(|slot| slot.is_some())
+        .unwrap_or(false)
+}
+
+pub fn stop() {
+    if let Ok(mut slot) = watcher_slot().lock() {
+        *slot = None;
+    }
+}
+
+// (Re)starts following the Codex auth file for the given state dir when its
+// sync setting is enabled, syncing once right away under `trigger`.
+pub fn start(app: tauri::AppHandle, state_dir: PathBuf, trigger: &str) -> Result<(), String> {
+    stop();
+    let settings = load_settings(&state_dir);
+    if !settings.enabled {
+        return Ok(());
+    }
+
+    let codex_auth_path = crate::resolve_codex_auth_path();
+    let codex_dir = codex_auth_path
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "Codex auth path has no parent directory.".to_string())?;
+    if !codex_dir.is_dir() {
+        return Err(format!(
+            "Codex auth directory {} does not exist; log in with the Codex CLI first.",
+            codex_dir.to_string_lossy()
+        ));
+    }
+
+    if let Some(entry) = sync_once(&state_dir, settings.agent_id.as_deref(), trigger) {
+        let _ = app.emit(CODEX_SYNC_EVENT, entry);
+    }
+
+    // OpenClaw refreshes tokens in the agents' auth-profiles.json, so those
+    // directories are followed too for the way back to Codex.
+    let agent_dirs: Vec<PathBuf> = crate::resolve_agent_auth_targets(settings.agent_id.as_deref())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(_, path)| path.parent().map(Path::to_path_buf))
+        .filter(|dir| dir.is_dir())
+        .collect();
+
+    let mut last_fingerprint = codex_fingerprint(&codex_auth_path).map(|(hash, _)| hash);
+    let watched_path = codex_auth_path.clone();
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, move |result: DebounceEventResult| {
+        let Ok(events) = result else {
+            return;
+        };
+        let codex_touched = events
+            .iter()
+            .any(|event| event.path.file_name() == watched_path.file_name());
+        let openclaw_touched = events.iter().any(|event| {
+            event.path.file_name().and_then(|name| name.to_str()) == Some(AUTH_PROFILES_FILE)
+        });
+        let fingerprint = codex_fingerprint(&watched_path).map(|(hash, _)| hash);
+        let trigger = if codex_touched && fingerprint.is_some() && fingerprint != last_fingerprint {
+            "file-change"
+        } else if openclaw_touched {
+            "openclaw-change"
+        } else {
+            return;
+        };
+        last_fingerprint = fingerprint;
+        if let Some(entry) = sync_once(&state_dir, settings.agent_id.as_deref(), trigger) {
+            let _ = app.emit(CODEX_SYNC_EVENT, entry);
+        }
+    })
+    .map_err(|err| format!("Failed to create Codex auth watcher: {}", err))?;
+    for dir in std::iter::once(&codex_dir).chain(agent_dirs.iter()) {
+        debouncer
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|err| format!("Failed to watch {}: {}", dir.to_string_lossy(), err))?;
+    }
+
+    let mut slot = watcher_slot()
+        .lock()
+        .map_err(|_| "Codex sync watcher lock poisoned".to_string())?;
+    *slot = Some(debouncer);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_refresh_is_formatted_like_codex_writes_it() {
+        assert_eq!(rfc3339_utc(0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(rfc3339_utc(1_709_164_800_123), "2024-02-29T00:00:00.123Z");
+        assert_eq!(rfc3339_utc(1_767_225_599_999), "2025-12-31T23:59:59.999Z");
+    }
+
+    #[test]
+    fn write_back_replaces_the_codex_tokens() {
+        let dir = std::env::temp_dir().join(format!("openclaw-codex-sync-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("auth.json");
+        fs::write(
+            &path,
+            r#"{
+  "OPENAI_API_KEY": null,
+  "tokens": { "id_token": "id-1", "access_token": "at-1", "refresh_token": "rt-1", "account_id": "acct-1" },
+  "last_refresh": "2025-01-01T00:00:00.000Z"
+}"#,
+        )
+        .unwrap();
+        let credential = serde_json::json!({
+            "type": "oauth",
+            "provider": "openai-codex",
+            "access": "at-2",
+            "refresh": "rt-2",
+            "expires": 1,
+            "accountId": "acct-2"
+        });
+
+        write_back_to_codex(&path, &credential).unwrap();
+        let auth: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(auth["tokens"]["access_token"], "at-2");
+        assert_eq!(auth["tokens"]["refresh_token"], "rt-2");
+        assert_eq!(auth["tokens"]["account_id"], "acct-2");
+        assert_eq!(auth["tokens"]["id_token"], "id-1");
+        assert!(auth["OPENAI_API_KEY"].is_null());
+        assert_ne!(auth["last_refresh"], "2025-01-01T00:00:00.000Z");
+    }
+
+    #[test]
+    fn write_back_needs_both_tokens() {
+        let credential = serde_json::json!({ "type": "oauth", "access": "at-2" });
+        assert!(write_back_to_codex(Path::new("/nonexistent/auth.json"), &credential).is_err());
+    }
+}
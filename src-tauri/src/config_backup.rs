@@ -0,0 +1,414 @@
+use crate::auth_profiles::SECRET_FIELDS;
+use crate::util::now_millis;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MAX_BACKUPS_PER_SOURCE: usize = 20;
+// Stands in for secret values in backups of files that hold credentials.
+pub const REDACTED_SECRET: &str = "<redacted>";
+const DIFF_CONTEXT_LINES: usize = 3;
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBackupEntry {
+    pub id: String,
+    pub kind: String,
+    pub source_path: String,
+    pub created_at_ms: i64,
+    pub reason: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigBackupFile {
+    #[serde(flatten)]
+    entry: ConfigBackupEntry,
+    content: String,
+}
+
+fn backup_path(backups_dir: &Path, id: &str) -> Result<PathBuf, String> {
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    {
+        return Err(format!("Invalid backup id: {}", id));
+    }
+    Ok(backups_dir.join(format!("{}.json", id)))
+}
+
+fn read_backup_file(path: &Path) -> Option<ConfigBackupFile> {
+    let raw = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<ConfigBackupFile>(&raw).ok()
+}
+
+fn read_all(backups_dir: &Path) -> Vec<ConfigBackupFile> {
+    let Ok(entries) = fs::read_dir(backups_dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<ConfigBackupFile> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| read_backup_file(&path))
+        .collect();
+    backups.sort_by(|a, b| {
+        b.entry
+            .created_at_ms
+            .cmp(&a.entry.created_at_ms)
+            .then_with(|| b.entry.id.cmp(&a.entry.id))
+    });
+    backups
+}
+
+// Newest first.
+pub fn list(backups_dir: &Path) -> Vec<ConfigBackupEntry> {
+    read_all(backups_dir)
+        .into_iter()
+        .map(|backup| backup.entry)
+        .collect()
+}
+
+pub fn load(backups_dir: &Path, id: &str) -> Result<(ConfigBackupEntry, String), String> {
+    let path = backup_path(backups_dir, id)?;
+    let backup = read_backup_file(&path).ok_or_else(|| format!("Backup {} not found", id))?;
+    Ok((backup.entry, backup.content))
+}
+
+fn profiles_mut(value: &mut Value) -> impl Iterator<Item = (&String, &mut Value)> {
+    value
+        .get_mut("profiles")
+        .and_then(Value::as_object_mut)
+        .into_iter()
+        .flat_map(|profiles| profiles.iter_mut())
+}
+
+// auth-profiles.json with every secret field replaced by REDACTED_SECRET, or
+// None when the file does not parse and so cannot be redacted.
+pub fn redact_secrets(content: &str) -> Option<String> {
+    let mut value = serde_json::from_str::<Value>(content).ok()?;
+    for (_, credential) in profiles_mut(&mut value) {
+        let Some(credential) = credential.as_object_mut() else {
+            continue;
+        };
+        for field in SECRET_FIELDS {
+            if credential.get(*field).is_some_and(Value::is_string) {
+                credential.insert(field.to_string(), Value::from(REDACTED_SECRET));
+            }
+        }
+    }
+    serde_json::to_string_pretty(&value).ok()
+}
+
+// Refills the secrets of a redacted auth-profiles backup from the file it
+// replaces. Secrets of profiles that no longer exist there are dropped, so
+// those profiles need a new login after the restore.
+pub fn restore_secrets(backup: &str, current: Option<&str>) -> Result<String, String> {
+    let mut value = serde_json::from_str::<Value>(backup)
+        .map_err(|err| format!("Backup is not valid JSON: {}", err))?;
+    let current = current.and_then(|text| serde_json::from_str::<Value>(text).ok());
+    for (profile_id, credential) in profiles_mut(&mut value) {
+        let Some(credential) = credential.as_object_mut() else {
+            continue;
+        };
+        for field in SECRET_FIELDS {
+            if credential.get(*field).and_then(Value::as_str) != Some(REDACTED_SECRET) {
+                continue;
+            }
+            let secret = current
+                .as_ref()
+                .and_then(|current| current.get("profiles")?.get(profile_id)?.get(*field))
+                .filter(|secret| secret.as_str() != Some(REDACTED_SECRET))
+                .cloned();
+            match secret {
+                Some(secret) => credential.insert(field.to_string(), secret),
+                None => credential.remove(*field),
+            };
+        }
+    }
+    serde_json::to_string_pretty(&value)
+        .map_err(|err| format!("Failed to serialize restored backup: {}", err))
+}
+
+// Copies the current content of `source` into the backups dir. Skips missing
+// sources and content identical to the newest backup of the same file, then
+// prunes that file's history to the newest MAX_BACKUPS_PER_SOURCE entries.
+// With `redact`, secret fields are blanked out first; a file that does not
+// parse is then not backed up at all, so no plaintext secret is ever copied.
+pub fn snapshot(
+    backups_dir: &Path,
+    kind: &str,
+    source: &Path,
+    reason: &str,
+    redact: bool,
+) -> Result<Option<ConfigBackupEntry>, String> {
+    if !source.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(source)
+        .map_err(|err| format!("Failed to read {}: {}", source.to_string_lossy(), err))?;
+    let content = if redact {
+        match redact_secrets(&content) {
+            Some(redacted) => redacted,
+            None => return Ok(None),
+        }
+    } else {
+        content
+    };
+    let source_path = source.to_string_lossy().to_string();
+
+    let existing: Vec<ConfigBackupFile> = read_all(backups_dir)
+        .into_iter()
+        .filter(|backup| backup.entry.source_path == source_path)
+        .collect();
+    if existing
+        .first()
+        .is_some_and(|latest| latest.content == content)
+    {
+        return Ok(None);
+    }
+
+    fs::create_dir_all(backups_dir).map_err(|err| {
+        format!(
+            "Failed to create backup dir {}: {}",
+            backups_dir.to_string_lossy(),
+            err
+        )
+    })?;
+
+    let created_at_ms = now_millis();
+    let mut id = format!("{}-{}", created_at_ms, kind);
+    let mut suffix = 1;
+    while backups_dir.join(format!("{}.json", id)).exists() {
+        suffix += 1;
+        id = format!("{}-{}-{}", created_at_ms, kind, suffix);
+    }
+
+    let backup = ConfigBackupFile {
+        entry: ConfigBackupEntry {
+            id: id.clone(),
+            kind: kind.to_string(),
+            source_path,
+            created_at_ms,
+            reason: reason.to_string(),
+            size_bytes: content.len() as u64,
+        },
+        content,
+    };
+    let serialized = serde_json::to_string_pretty(&backup)
+        .map_err(|err| format!("Failed to serialize backup: {}", err))?;
+    // Configs can still hold provider keys, so backups are always private.
+    crate::config_txn::write_atomic(&backup_path(backups_dir, &id)?, &serialized, true)?;
+
+    for stale in existing.iter().skip(MAX_BACKUPS_PER_SOURCE - 1) {
+        if let Ok(path) = backup_path(backups_dir, &stale.entry.id) {
+            let _ = fs::remove_file(path);
+        }
+    }
+    Ok(Some(backup.entry))
+}
+
+enum DiffOp<'a> {
+    Equal(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffOp<'a>> {
+    let (n, m) = (old.len(), new.len());
+    if n.saturating_mul(m) > MAX_DIFF_CELLS {
+        let mut ops: Vec<DiffOp<'a>> = old.iter().map(|line| DiffOp::Removed(line)).collect();
+        ops.extend(new.iter().map(|line| DiffOp::Added(line)));
+        return ops;
+    }
+
+    // lcs[i][j] = length of the longest common subsequence of old[i..] and new[j..].
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            ops.push(DiffOp::Equal(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            ops.push(DiffOp::Removed(old[i]));
+            i += 1;
+        } else {
+            ops.push(DiffOp::Added(new[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old[i..].iter().map(|line| DiffOp::Removed(line)));
+    ops.extend(new[j..].iter().map(|line| DiffOp::Added(line)));
+    ops
+}
+
+// Line-based unified diff; empty when both sides are identical.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&old_lines, &new_lines);
+    if ops.iter().all(|op| matches!(op, DiffOp::Equal(_))) {
+        return String::new();
+    }
+
+    let changed: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, DiffOp::Equal(_)))
+        .map(|(idx, _)| idx)
+        .collect();
+
+    // Group changes whose context windows touch into hunks of op indexes.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &idx in &changed {
+        let start = idx.saturating_sub(DIFF_CONTEXT_LINES);
+        let end = (idx + DIFF_CONTEXT_LINES + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut output = format!("--- {}\n+++ {}\n", old_label, new_label);
+    let (mut old_line, mut new_line) = (1usize, 1usize);
+    let mut cursor = 0usize;
+    for (start, end) in hunks {
+        for op in &ops[cursor..start] {
+            match op {
+                DiffOp::Equal(_) => {
+                    old_line += 1;
+                    new_line += 1;
+                }
+                DiffOp::Removed(_) => old_line += 1,
+                DiffOp::Added(_) => new_line += 1,
+            }
+        }
+        let mut body = String::new();
+        let (mut old_count, mut new_count) = (0usize, 0usize);
+        for op in &ops[start..end] {
+            match op {
+                DiffOp::Equal(line) => {
+                    body.push_str(&format!(" {}\n", line));
+                    old_count += 1;
+                    new_count += 1;
+                }
+                DiffOp::Removed(line) => {
+                    body.push_str(&format!("-{}\n", line));
+                    old_count += 1;
+                }
+                DiffOp::Added(line) => {
+                    body.push_str(&format!("+{}\n", line));
+                    new_count += 1;
+                }
+            }
+        }
+        output.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n{}",
+            if old_count == 0 {
+                old_line - 1
+            } else {
+                old_line
+            },
+            old_count,
+            if new_count == 0 {
+                new_line - 1
+            } else {
+                new_line
+            },
+            new_count,
+            body
+        ));
+        old_line += old_count;
+        new_line += new_count;
+        cursor = end;
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTH_PROFILES: &str = r#"{
+  "version": 1,
+  "profiles": {
+    "openai:default": { "type": "api_key", "provider": "openai", "key": "sk-live" },
+    "openai-codex:default": {
+      "type": "oauth",
+      "provider": "openai-codex",
+      "access": "at-1",
+      "refresh": "rt-1",
+      "expires": 1
+    }
+  }
+}"#;
+
+    #[test]
+    fn snapshot_redacts_auth_profile_secrets() {
+        let dir = std::env::temp_dir().join(format!("openclaw-backup-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("auth-profiles.json");
+        fs::write(&source, AUTH_PROFILES).unwrap();
+        let backups_dir = dir.join("backups");
+
+        let entry = snapshot(&backups_dir, "auth-profiles", &source, "test", true)
+            .unwrap()
+            .unwrap();
+        let raw = fs::read_to_string(backups_dir.join(format!("{}.json", entry.id))).unwrap();
+        for secret in ["sk-live", "at-1", "rt-1"] {
+            assert!(!raw.contains(secret), "{} leaked into the backup", secret);
+        }
+        let (_, content) = load(&backups_dir, &entry.id).unwrap();
+        let parsed: Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(
+            parsed.pointer("/profiles/openai:default/key"),
+            Some(&Value::from(REDACTED_SECRET))
+        );
+        assert_eq!(
+            parsed.pointer("/profiles/openai-codex:default/expires"),
+            Some(&Value::from(1))
+        );
+
+        fs::write(&source, "{ not json").unwrap();
+        assert!(
+            snapshot(&backups_dir, "auth-profiles", &source, "test", true)
+                .unwrap()
+                .is_none()
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn restore_refills_secrets_from_the_current_file() {
+        let redacted = redact_secrets(AUTH_PROFILES).unwrap();
+        let current = r#"{ "profiles": { "openai:default": { "key": "sk-rotated" } } }"#;
+
+        let restored: Value =
+            serde_json::from_str(&restore_secrets(&redacted, Some(current)).unwrap()).unwrap();
+        assert_eq!(
+            restored.pointer("/profiles/openai:default/key"),
+            Some(&Value::from("sk-rotated"))
+        );
+        let codex = restored.pointer("/profiles/openai-codex:default").unwrap();
+        assert!(codex.get("access").is_none());
+        assert!(codex.get("refresh").is_none());
+        assert_eq!(codex.get("type"), Some(&Value::from("oauth")));
+    }
+}
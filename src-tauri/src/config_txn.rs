@@ -0,0 +1,224 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// Read-modify-write on one config file while holding an advisory lock on a
+// sidecar `<file>.lock`. Only the desktop takes that lock: it serializes the
+// app's own writers (commands, watchers, sync threads), while the openclaw
+// CLI writes without it and is only kept from seeing half-written files by
+// the atomic rename. The lock is released when the transaction drops.
+pub struct ConfigTransaction {
+    path: PathBuf,
+    private: bool,
+    backup: Option<BackupTarget>,
+    _lock: File,
+}
+
+struct BackupTarget {
+    dir: PathBuf,
+    kind: String,
+    reason: String,
+}
+
+impl ConfigTransaction {
+    pub fn begin(path: &Path) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|err| {
+                format!(
+                    "Failed to create config dir {}: {}",
+                    parent.to_string_lossy(),
+                    err
+                )
+            })?;
+        }
+
+        let lock_path = sidecar_path(path, "lock");
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .map_err(|err| format!("Failed to open {}: {}", lock_path.to_string_lossy(), err))?;
+        lock.lock()
+            .map_err(|err| format!("Failed to lock {}: {}", path.to_string_lossy(), err))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            private: false,
+            backup: None,
+            _lock: lock,
+        })
+    }
+
+    // Committed file is restricted to the current user (0600 on unix), and its
+    // backups have their secret fields redacted.
+    pub fn private(mut self) -> Self {
+        self.private = true;
+        self
+    }
+
+    // The previous file content is snapshotted into `dir` before each commit.
+    pub fn backup_to(mut self, dir: &Path, kind: &str, reason: &str) -> Self {
+        self.backup = Some(BackupTarget {
+            dir: dir.to_path_buf(),
+            kind: kind.to_string(),
+            reason: reason.to_string(),
+        });
+        self
+    }
+
+    pub fn read(&self) -> Result<Option<String>, String> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        fs::read_to_string(&self.path)
+            .map(Some)
+            .map_err(|err| format!("Failed to read {}: {}", self.path.to_string_lossy(), err))
+    }
+
+    pub fn commit(&self, contents: &str) -> Result<(), String> {
+        if let Some(backup) = &self.backup {
+            crate::config_backup::snapshot(
+                &backup.dir,
+                &backup.kind,
+                &self.path,
+                &backup.reason,
+                self.private,
+            )?;
+        }
+        write_atomic(&self.path, contents, self.private)
+    }
+}
+
+fn sidecar_path(path: &Path, suffix: &str) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config".to_string());
+    path.with_file_name(format!("{}.{}", file_name, suffix))
+}
+
+// Writes to a temp file in the same directory, fsyncs it and renames it over
+// the target, so readers never observe a half-written file.
+pub fn write_atomic(path: &Path, contents: &str, private: bool) -> Result<(), String> {
+    // Unique per call, so concurrent writers in this process never share a
+    // temp file.
+    let tmp_path = sidecar_path(
+        path,
+        &format!(
+            "tmp-{}-{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ),
+    );
+    let result = (|| {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&tmp_path)
+            .map_err(|err| format!("Failed to create {}: {}", tmp_path.to_string_lossy(), err))?;
+        #[cfg(unix)]
+        {
+            if private {
+                fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600))
+                    .map_err(|err| err.to_string())?;
+            } else if let Ok(meta) = fs::metadata(path) {
+                fs::set_permissions(&tmp_path, meta.permissions())
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = private;
+        file.write_all(contents.as_bytes())
+            .map_err(|err| format!("Failed to write {}: {}", tmp_path.to_string_lossy(), err))?;
+        file.sync_all()
+            .map_err(|err| format!("Failed to sync {}: {}", tmp_path.to_string_lossy(), err))?;
+        fs::rename(&tmp_path, path)
+            .map_err(|err| format!("Failed to replace {}: {}", path.to_string_lossy(), err))
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+        return result;
+    }
+
+    #[cfg(unix)]
+    {
+        if let Some(parent) = path.parent() {
+            if let Ok(dir) = File::open(parent) {
+                let _ = dir.sync_all();
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_atomic_writes_do_not_share_temp_files() {
+        let dir = std::env::temp_dir().join(format!("openclaw-config-txn-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("openclaw.json");
+
+        let writers: Vec<_> = (0..8)
+            .map(|index| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let contents = format!("{{\"writer\": {}}}", index).repeat(2000);
+                    for _ in 0..20 {
+                        write_atomic(&path, &contents, false).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let written = fs::read_to_string(&path).unwrap();
+        let first = &written[..written.find('}').unwrap() + 1];
+        assert_eq!(written, first.repeat(2000));
+        let leftovers = fs::read_dir(&dir)
+            .unwrap()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().contains(".tmp-"))
+            .count();
+        assert_eq!(leftovers, 0);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn transactions_serialize_read_modify_write() {
+        let dir = std::env::temp_dir().join(format!("openclaw-config-lock-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("counter.json");
+        write_atomic(&path, "0", false).unwrap();
+
+        let workers: Vec<_> = (0..4)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..25 {
+                        let txn = ConfigTransaction::begin(&path).unwrap();
+                        let value: u32 = txn.read().unwrap().unwrap().parse().unwrap();
+                        txn.commit(&(value + 1).to_string()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "100");
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,402 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+// Top-level sections OpenClaw understands; anything else is most likely a typo.
+const KNOWN_SECTIONS: &[&str] = &[
+    "$schema",
+    "meta",
+    "wizard",
+    "env",
+    "update",
+    "diagnostics",
+    "logging",
+    "auth",
+    "models",
+    "agents",
+    "tools",
+    "bindings",
+    "broadcast",
+    "audio",
+    "media",
+    "messages",
+    "commands",
+    "approvals",
+    "session",
+    "cron",
+    "hooks",
+    "web",
+    "channels",
+    "discovery",
+    "canvasHost",
+    "talk",
+    "gateway",
+    "browser",
+    "ui",
+    "skills",
+    "plugins",
+    "nodeHost",
+];
+
+const AUTH_MODES: &[&str] = &["api_key", "oauth", "token"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDiagnostic {
+    pub severity: Severity,
+    pub pointer: String,
+    pub message: String,
+    pub hint: Option<String>,
+}
+
+pub fn is_valid_tcp_port(port: i64) -> bool {
+    port > 0 && port <= 65535
+}
+
+fn pointer(segments: &[&str]) -> String {
+    segments
+        .iter()
+        .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+struct Validator {
+    diagnostics: Vec<ConfigDiagnostic>,
+}
+
+impl Validator {
+    fn push(&mut self, severity: Severity, path: &[&str], message: String, hint: Option<&str>) {
+        self.diagnostics.push(ConfigDiagnostic {
+            severity,
+            pointer: pointer(path),
+            message,
+            hint: hint.map(str::to_string),
+        });
+    }
+
+    fn expect_type(&mut self, value: &Value, path: &[&str], expected: &str) -> bool {
+        let actual = type_name(value);
+        if actual == expected {
+            return true;
+        }
+        self.push(
+            Severity::Error,
+            path,
+            format!("Expected {}, found {}.", expected, actual),
+            None,
+        );
+        false
+    }
+
+    fn object<'v>(&mut self, value: &'v Value, path: &[&str]) -> Option<&'v Map<String, Value>> {
+        if self.expect_type(value, path, "object") {
+            value.as_object()
+        } else {
+            None
+        }
+    }
+
+    fn non_empty_string(&mut self, value: &Value, path: &[&str]) -> Option<String> {
+        if !self.expect_type(value, path, "string") {
+            return None;
+        }
+        let text = value.as_str().unwrap_or_default().trim().to_string();
+        if text.is_empty() {
+            self.push(Severity::Warning, path, "Value is empty.".to_string(), None);
+            return None;
+        }
+        Some(text)
+    }
+
+    fn port(&mut self, value: &Value, path: &[&str]) {
+        match value.as_i64() {
+            Some(port) if is_valid_tcp_port(port) => {}
+            Some(port) => self.push(
+                Severity::Error,
+                path,
+                format!("Port {} is outside 1-65535.", port),
+                None,
+            ),
+            None => {
+                self.expect_type(value, path, "integer");
+            }
+        }
+    }
+
+    fn validate_auth(&mut self, auth: &Value, stored_profile_ids: Option<&BTreeSet<String>>) {
+        let Some(auth) = self.object(auth, &["auth"]) else {
+            return;
+        };
+
+        let mut profile_providers: Vec<(String, Option<String>)> = Vec::new();
+        if let Some(profiles) = auth.get("profiles") {
+            if let Some(profiles) = self.object(profiles, &["auth", "profiles"]) {
+                for (profile_id, profile) in profiles {
+                    let path = ["auth", "profiles", profile_id.as_str()];
+                    let Some(profile) = self.object(profile, &path) else {
+                        continue;
+                    };
+                    let provider = match profile.get("provider") {
+                        Some(provider) => self.non_empty_string(
+                            provider,
+                            &["auth", "profiles", profile_id, "provider"],
+                        ),
+                        None => {
+                            self.push(
+                                Severity::Error,
+                                &path,
+                                "Auth profile has no provider.".to_string(),
+                                None,
+                            );
+                            None
+                        }
+                    };
+                    if let Some(mode) = profile.get("mode") {
+                        let mode_path = ["auth", "profiles", profile_id.as_str(), "mode"];
+                        if let Some(mode) = self.non_empty_string(mode, &mode_path) {
+                            if !AUTH_MODES.contains(&mode.as_str()) {
+                                self.push(
+                                    Severity::Warning,
+                                    &mode_path,
+                                    format!("Unknown auth mode '{}'.", mode),
+                                    Some("Use one of: api_key, oauth, token."),
+                                );
+                            }
+                        }
+                    }
+                    if stored_profile_ids.is_some_and(|ids| !ids.contains(profile_id)) {
+                        self.push(
+                            Severity::Warning,
+                            &path,
+                            format!("No credential for '{}' in auth-profiles.json.", profile_id),
+                            Some("Log in again or save the API key for this provider."),
+                        );
+                    }
+                    profile_providers.push((profile_id.clone(), provider));
+                }
+            }
+        }
+
+        let Some(order) = auth.get("order") else {
+            return;
+        };
+        let Some(order) = self.object(order, &["auth", "order"]) else {
+            return;
+        };
+        for (provider_id, ids) in order {
+            let Some(ids) = ids.as_array() else {
+                self.expect_type(ids, &["auth", "order", provider_id], "array");
+                continue;
+            };
+            for (idx, id) in ids.iter().enumerate() {
+                let index = idx.to_string();
+                let path = ["auth", "order", provider_id.as_str(), index.as_str()];
+                let Some(id) = self.non_empty_string(id, &path) else {
+                    continue;
+                };
+                match profile_providers.iter().find(|(known, _)| *known == id) {
+                    Some((_, Some(provider))) if provider != provider_id => self.push(
+                        Severity::Warning,
+                        &path,
+                        format!(
+                            "Profile '{}' belongs to provider '{}', not '{}'.",
+                            id, provider, provider_id
+                        ),
+                        None,
+                    ),
+                    Some(_) => {}
+                    None => {
+                        let stored = stored_profile_ids.is_some_and(|ids| ids.contains(&id));
+                        self.push(
+                            if stored {
+                                Severity::Warning
+                            } else {
+                                Severity::Error
+                            },
+                            &path,
+                            format!("Profile '{}' is not defined in auth.profiles.", id),
+                            Some(if stored {
+                                "Add it to auth.profiles so OpenClaw knows its provider and mode."
+                            } else {
+                                "Remove it from auth.order or log in to recreate the profile."
+                            }),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_agents(&mut self, agents: &Value) {
+        let Some(agents) = self.object(agents, &["agents"]) else {
+            return;
+        };
+        let Some(defaults) = agents.get("defaults") else {
+            return;
+        };
+        let Some(defaults) = self.object(defaults, &["agents", "defaults"]) else {
+            return;
+        };
+        let Some(model) = defaults.get("model") else {
+            return;
+        };
+
+        let path = ["agents", "defaults", "model"];
+        let primary = match model {
+            Value::String(_) => self
+                .non_empty_string(model, &path)
+                .map(|id| (id, path.to_vec())),
+            Value::Object(model_obj) => match model_obj.get("primary") {
+                Some(primary) => {
+                    let primary_path = ["agents", "defaults", "model", "primary"];
+                    self.non_empty_string(primary, &primary_path)
+                        .map(|id| (id, primary_path.to_vec()))
+                }
+                None => {
+                    self.push(
+                        Severity::Warning,
+                        &path,
+                        "No primary model is set.".to_string(),
+                        None,
+                    );
+                    None
+                }
+            },
+            other => {
+                self.push(
+                    Severity::Error,
+                    &path,
+                    format!("Expected string or object, found {}.", type_name(other)),
+                    None,
+                );
+                None
+            }
+        };
+        if let Some((id, primary_path)) = primary {
+            if !id.contains('/') {
+                self.push(
+                    Severity::Warning,
+                    &primary_path,
+                    format!("Model '{}' has no provider prefix.", id),
+                    Some("Use the provider/model form, e.g. openai/gpt-5."),
+                );
+            }
+        }
+    }
+
+    fn validate_browser(&mut self, browser: &Value) {
+        let Some(browser) = self.object(browser, &["browser"]) else {
+            return;
+        };
+        if let Some(enabled) = browser.get("enabled") {
+            self.expect_type(enabled, &["browser", "enabled"], "boolean");
+        }
+        if let Some(profile) = browser.get("defaultProfile") {
+            self.non_empty_string(profile, &["browser", "defaultProfile"]);
+        }
+        if let Some(executable) = browser.get("executablePath") {
+            let path = ["browser", "executablePath"];
+            if let Some(executable) = self.non_empty_string(executable, &path) {
+                if !Path::new(&executable).is_file() {
+                    self.push(
+                        Severity::Error,
+                        &path,
+                        format!("Browser executable {} does not exist.", executable),
+                        Some("Switch browser mode again to re-detect an installed browser."),
+                    );
+                }
+            }
+        }
+
+        let Some(profiles) = browser.get("profiles") else {
+            return;
+        };
+        let Some(profiles) = self.object(profiles, &["browser", "profiles"]) else {
+            return;
+        };
+        for (name, profile) in profiles {
+            let Some(profile) = self.object(profile, &["browser", "profiles", name]) else {
+                continue;
+            };
+            if let Some(port) = profile.get("cdpPort") {
+                self.port(port, &["browser", "profiles", name, "cdpPort"]);
+            }
+            if let Some(url) = profile.get("cdpUrl") {
+                let path = ["browser", "profiles", name.as_str(), "cdpUrl"];
+                if let Some(url) = self.non_empty_string(url, &path) {
+                    let lower = url.to_ascii_lowercase();
+                    if !["http://", "https://", "ws://", "wss://"]
+                        .iter()
+                        .any(|scheme| lower.starts_with(scheme))
+                    {
+                        self.push(
+                            Severity::Error,
+                            &path,
+                            format!("'{}' is not an http(s) or ws(s) URL.", url),
+                            None,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_gateway(&mut self, gateway: &Value) {
+        let Some(gateway) = self.object(gateway, &["gateway"]) else {
+            return;
+        };
+        if let Some(port) = gateway.get("port") {
+            self.port(port, &["gateway", "port"]);
+        }
+    }
+}
+
+// `stored_profile_ids` are the credential ids in auth-profiles.json, when readable.
+pub fn validate(
+    config: &Value,
+    stored_profile_ids: Option<&BTreeSet<String>>,
+) -> Vec<ConfigDiagnostic> {
+    let mut validator = Validator {
+        diagnostics: Vec::new(),
+    };
+    let Some(root) = validator.object(config, &[]) else {
+        return validator.diagnostics;
+    };
+
+    for (key, value) in root {
+        match key.as_str() {
+            "auth" => validator.validate_auth(value, stored_profile_ids),
+            "agents" => validator.validate_agents(value),
+            "browser" => validator.validate_browser(value),
+            "gateway" => validator.validate_gateway(value),
+            known if KNOWN_SECTIONS.contains(&known) => {}
+            unknown => validator.push(
+                Severity::Warning,
+                &[unknown],
+                format!("Unknown top-level section '{}'.", unknown),
+                Some("OpenClaw ignores it; check for a typo."),
+            ),
+        }
+    }
+    validator.diagnostics
+}
@@ -0,0 +1,173 @@
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::Emitter;
+
+pub const CONFIG_CHANGED_EVENT: &str = "config-changed";
+pub const AUTH_PROFILES_CHANGED_EVENT: &str = "auth-profiles-changed";
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(400);
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedFileChange {
+    pub path: String,
+    // "ok", "missing" or "corrupt", same as get_openclaw_config_status.
+    pub state: String,
+    // Top-level keys that changed; entries of `nested_key` are reported as
+    // `<nested_key>.<id>` (e.g. `profiles.openai:default`).
+    pub changed_sections: Vec<String>,
+}
+
+struct WatchedFile {
+    path: PathBuf,
+    event: &'static str,
+    nested_key: Option<&'static str>,
+    last: Option<Value>,
+    last_state: &'static str,
+}
+
+impl WatchedFile {
+    fn new(path: PathBuf, event: &'static str, nested_key: Option<&'static str>) -> Self {
+        let (last_state, last) = match read_value(&path) {
+            Ok(Some(value)) => ("ok", Some(value)),
+            Ok(None) => ("missing", None),
+            Err(()) => ("corrupt", None),
+        };
+        Self {
+            path,
+            event,
+            nested_key,
+            last,
+            last_state,
+        }
+    }
+
+    fn is_touched_by(&self, changed: &Path) -> bool {
+        changed.file_name().is_some() && changed.file_name() == self.path.file_name()
+    }
+
+    fn refresh(&mut self) -> Option<WatchedFileChange> {
+        let (state, current) = match read_value(&self.path) {
+            Ok(Some(value)) => ("ok", Some(value)),
+            Ok(None) => ("missing", None),
+            // Keep the last good snapshot so the next valid save diffs against it.
+            Err(()) => ("corrupt", self.last.clone()),
+        };
+        if state == self.last_state && current == self.last {
+            return None;
+        }
+
+        let changed_sections =
+            changed_sections(self.last.as_ref(), current.as_ref(), self.nested_key);
+        self.last = current;
+        self.last_state = state;
+        Some(WatchedFileChange {
+            path: self.path.to_string_lossy().to_string(),
+            state: state.to_string(),
+            changed_sections,
+        })
+    }
+}
+
+fn read_value(path: &Path) -> Result<Option<Value>, ()> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return if path.exists() { Err(()) } else { Ok(None) };
+    };
+    if content.trim().is_empty() {
+        return Ok(Some(serde_json::json!({})));
+    }
+    serde_json::from_str::<Value>(&content)
+        .or_else(|_| json5::from_str::<Value>(&content))
+        .map(Some)
+        .map_err(|_| ())
+}
+
+fn changed_sections(
+    old: Option<&Value>,
+    new: Option<&Value>,
+    nested_key: Option<&str>,
+) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let old = old.and_then(Value::as_object).unwrap_or(&empty);
+    let new = new.and_then(Value::as_object).unwrap_or(&empty);
+    let keys: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+
+    let mut sections = Vec::new();
+    for key in keys {
+        let (before, after) = (old.get(key), new.get(key));
+        if before == after {
+            continue;
+        }
+        if Some(key.as_str()) == nested_key {
+            let nested = changed_sections(before, after, None);
+            if !nested.is_empty() {
+                sections.extend(nested.into_iter().map(|id| format!("{}.{}", key, id)));
+                continue;
+            }
+        }
+        sections.push(key.clone());
+    }
+    sections
+}
+
+fn watcher_slot() -> &'static Mutex<Option<Debouncer<RecommendedWatcher>>> {
+    static SLOT: OnceLock<Mutex<Option<Debouncer<RecommendedWatcher>>>> = OnceLock::new();
+    SLOT.get_or_init(|| Mutex::new(None))
+}
+
+// (Re)starts watching both files. The parent directories are watched rather
+// than the files themselves, because atomic saves replace the file inode.
+pub fn start(
+    app: tauri::AppHandle,
+    config_path: PathBuf,
+    auth_profiles_path: PathBuf,
+) -> Result<(), String> {
+    let mut files = [
+        WatchedFile::new(config_path, CONFIG_CHANGED_EVENT, None),
+        WatchedFile::new(
+            auth_profiles_path,
+            AUTH_PROFILES_CHANGED_EVENT,
+            Some("profiles"),
+        ),
+    ];
+    let dirs: BTreeSet<PathBuf> = files
+        .iter()
+        .filter_map(|file| file.path.parent().map(Path::to_path_buf))
+        .collect();
+
+    let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, move |result: DebounceEventResult| {
+        let Ok(events) = result else {
+            return;
+        };
+        for file in files.iter_mut() {
+            if !events.iter().any(|event| file.is_touched_by(&event.path)) {
+                continue;
+            }
+            if let Some(change) = file.refresh() {
+                let _ = app.emit(file.event, change);
+            }
+        }
+    })
+    .map_err(|err| format!("Failed to create config watcher: {}", err))?;
+
+    for dir in dirs {
+        fs::create_dir_all(&dir)
+            .map_err(|err| format!("Failed to create {}: {}", dir.to_string_lossy(), err))?;
+        debouncer
+            .watcher()
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|err| format!("Failed to watch {}: {}", dir.to_string_lossy(), err))?;
+    }
+
+    let mut slot = watcher_slot()
+        .lock()
+        .map_err(|_| "Config watcher lock poisoned".to_string())?;
+    *slot = Some(debouncer);
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use std::net::TcpListener;
+
+pub const DEFAULT_GATEWAY_PORT: u16 = 18789;
+const DEFAULT_GATEWAY_HOST: &str = "127.0.0.1";
+// How far past the configured port to look for a free one.
+const PORT_SEARCH_SPAN: u16 = 100;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayEndpoint {
+    pub host: String,
+    pub port: u16,
+    // "env", "config", "default" or "profile".
+    pub source: String,
+    // `gateway.bind` from openclaw.json, handed to `openclaw gateway run`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bind: Option<String>,
+}
+
+// `gateway.bind` and `gateway.customBindHost` from openclaw.json.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GatewayBind {
+    pub mode: Option<String>,
+    pub custom_host: Option<String>,
+}
+
+impl GatewayBind {
+    // Loopback, lan and auto all accept connections on 127.0.0.1; only a
+    // custom bind names the address to dial.
+    fn connect_host(&self) -> Option<String> {
+        match self.mode.as_deref() {
+            Some("custom") => self.custom_host.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl GatewayEndpoint {
+    pub fn base_url(&self) -> String {
+        if self.host.contains(':') {
+            format!("http://[{}]:{}/", self.host, self.port)
+        } else {
+            format!("http://{}:{}/", self.host, self.port)
+        }
+    }
+}
+
+fn env_value(name: &str) -> Option<String> {
+    let value = std::env::var(name).ok()?;
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+fn parse_port(value: &str) -> Option<u16> {
+    value.parse::<u16>().ok().filter(|port| *port > 0)
+}
+
+// `OPENCLAW_GATEWAY_HOST` wins over the configured bind address.
+fn resolve_host(bind: &GatewayBind) -> String {
+    env_value("OPENCLAW_GATEWAY_HOST")
+        .or_else(|| bind.connect_host())
+        .unwrap_or_else(|| DEFAULT_GATEWAY_HOST.into())
+}
+
+// `OPENCLAW_GATEWAY_PORT` wins over `gateway.port`, same as the OpenClaw CLI.
+pub fn resolve(config_port: Option<u16>, bind: GatewayBind) -> GatewayEndpoint {
+    let host = resolve_host(&bind);
+    let (port, source) = match env_value("OPENCLAW_GATEWAY_PORT").and_then(|v| parse_port(&v)) {
+        Some(port) => (port, "env"),
+        None => match config_port {
+            Some(port) => (port, "config"),
+            None => (DEFAULT_GATEWAY_PORT, "default"),
+        },
+    };
+    GatewayEndpoint {
+        host,
+        port,
+        source: source.to_string(),
+        bind: bind.mode,
+    }
+}
+
+// A registered desktop profile pins its own port; env overrides only apply to
+// the profile the app was launched with.
+pub fn for_profile(port: u16, bind: GatewayBind) -> GatewayEndpoint {
+    GatewayEndpoint {
+        host: resolve_host(&bind),
+        port,
+        source: "profile".to_string(),
+        bind: bind.mode,
+    }
+}
+
+pub fn is_port_free(host: &str, port: u16) -> bool {
+    TcpListener::bind((host, port)).is_ok()
+}
+
+pub fn next_free_port(host: &str, after: u16) -> Option<u16> {
+    (1..=PORT_SEARCH_SPAN)
+        .filter_map(|offset| after.checked_add(offset))
+        .find(|port| is_port_free(host, *port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bind(mode: &str, custom_host: Option<&str>) -> GatewayBind {
+        GatewayBind {
+            mode: Some(mode.to_string()),
+            custom_host: custom_host.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn custom_bind_sets_the_host_to_dial() {
+        let endpoint = for_profile(19001, bind("custom", Some("10.0.0.5")));
+        assert_eq!(endpoint.host, "10.0.0.5");
+        assert_eq!(endpoint.bind.as_deref(), Some("custom"));
+        assert_eq!(endpoint.base_url(), "http://10.0.0.5:19001/");
+    }
+
+    #[test]
+    fn other_binds_dial_loopback() {
+        for mode in ["loopback", "lan", "auto"] {
+            let endpoint = for_profile(19001, bind(mode, Some("10.0.0.5")));
+            assert_eq!(endpoint.host, DEFAULT_GATEWAY_HOST);
+            assert_eq!(endpoint.bind.as_deref(), Some(mode));
+        }
+        let endpoint = for_profile(19001, GatewayBind::default());
+        assert_eq!(endpoint.host, DEFAULT_GATEWAY_HOST);
+        assert_eq!(endpoint.bind, None);
+    }
+}
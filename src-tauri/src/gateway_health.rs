@@ -0,0 +1,309 @@
+use crate::gateway_endpoint::{self, GatewayEndpoint};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const PROBE_TIMEOUT: Duration = Duration::from_millis(1200);
+// Gated by the gateway token and always enabled, so it both identifies an
+// OpenClaw gateway and checks the token. An empty request names no tool and
+// is rejected before anything runs.
+const AUTH_CHECK_PATH: &str = "tools/invoke";
+const VERSION_HEADER: &str = "x-openclaw-version";
+
+// Outcome of the auth check per port and token. The readiness poll probes
+// every few hundred milliseconds, so the authenticated POST is only repeated
+// once the port stopped answering in between.
+#[derive(Clone)]
+struct AuthCheck {
+    status: u16,
+    version: Option<String>,
+}
+
+// Keyed by port and token.
+type AuthChecks = BTreeMap<(u16, Option<String>), AuthCheck>;
+
+fn auth_checks() -> &'static Mutex<AuthChecks> {
+    static CHECKS: OnceLock<Mutex<AuthChecks>> = OnceLock::new();
+    CHECKS.get_or_init(|| Mutex::new(BTreeMap::new()))
+}
+
+fn forget_auth_checks(port: u16) {
+    if let Ok(mut checks) = auth_checks().lock() {
+        checks.retain(|(checked_port, _), _| *checked_port != port);
+    }
+}
+
+fn header_version(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get(VERSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+async fn check_auth(
+    client: &reqwest::Client,
+    url: &str,
+    token: Option<&str>,
+) -> Result<AuthCheck, String> {
+    let mut request = client
+        .post(format!("{}{}", url, AUTH_CHECK_PATH))
+        .json(&serde_json::json!({}));
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.map_err(|err| err.to_string())?;
+    let status = response.status().as_u16();
+    let mut version = header_version(&response);
+    if version.is_none() {
+        version = response
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|body| {
+                body.get("version")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            });
+    }
+    Ok(AuthCheck { status, version })
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum GatewayHealthState {
+    Ready,
+    // Nothing answers yet, but the app's own gateway process is running.
+    Starting,
+    // An OpenClaw gateway answers with an error or rejects the token.
+    Unhealthy,
+    // The port is held by something that is not an OpenClaw gateway.
+    PortInUse,
+    Down,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayHealthProbe {
+    pub state: GatewayHealthState,
+    pub url: String,
+    pub status_code: Option<u16>,
+    pub is_openclaw: bool,
+    // None when no token is configured or the gateway could not be reached.
+    pub token_accepted: Option<bool>,
+    pub latency_ms: Option<u64>,
+    // As reported by the gateway; None for gateways that do not report one.
+    pub version: Option<String>,
+    pub error: Option<String>,
+}
+
+// `owned_running` tells a gateway that is still booting apart from no gateway.
+pub async fn probe(
+    endpoint: &GatewayEndpoint,
+    token: Option<&str>,
+    owned_running: bool,
+) -> GatewayHealthProbe {
+    let url = endpoint.base_url();
+    let mut result = GatewayHealthProbe {
+        state: GatewayHealthState::Down,
+        url: url.clone(),
+        status_code: None,
+        is_openclaw: false,
+        token_accepted: None,
+        latency_ms: None,
+        version: None,
+        error: None,
+    };
+
+    let client = match reqwest::Client::builder().timeout(PROBE_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => {
+            result.error = Some(format!("Failed to build HTTP client: {}", err));
+            return result;
+        }
+    };
+    let started = Instant::now();
+    let response = match client.get(&url).send().await {
+        Ok(response) => response,
+        Err(err) => {
+            forget_auth_checks(endpoint.port);
+            result.error = Some(err.to_string());
+            result.state = if owned_running {
+                GatewayHealthState::Starting
+            } else if !gateway_endpoint::is_port_free(&endpoint.host, endpoint.port) {
+                GatewayHealthState::PortInUse
+            } else {
+                GatewayHealthState::Down
+            };
+            return result;
+        }
+    };
+    result.latency_ms = Some(started.elapsed().as_millis() as u64);
+    let status = response.status();
+    result.status_code = Some(status.as_u16());
+    let root_version = header_version(&response);
+
+    let key = (endpoint.port, token.map(str::to_string));
+    let cached = auth_checks()
+        .lock()
+        .ok()
+        .and_then(|checks| checks.get(&key).cloned());
+    let auth_check = match cached {
+        Some(check) => check,
+        None => match check_auth(&client, &url, token).await {
+            Ok(check) => {
+                // A gateway answering with a server error may recover, so
+                // only settled answers are kept.
+                if check.status < 500 {
+                    if let Ok(mut checks) = auth_checks().lock() {
+                        checks.insert(key, check.clone());
+                    }
+                }
+                check
+            }
+            Err(err) => {
+                result.error = Some(format!(
+                    "The gateway did not answer an authenticated request: {}",
+                    err
+                ));
+                result.state = GatewayHealthState::Unhealthy;
+                return result;
+            }
+        },
+    };
+    let auth_status = auth_check.status;
+
+    // Servers other than an OpenClaw gateway have no such route.
+    result.is_openclaw = !matches!(auth_status, 404 | 405);
+    if result.is_openclaw {
+        result.version = root_version.or(auth_check.version);
+    }
+    let auth_rejected = matches!(auth_status, 401 | 403);
+    if result.is_openclaw && token.is_some() {
+        result.token_accepted = Some(!auth_rejected);
+    }
+
+    result.state = if !result.is_openclaw {
+        result.error = Some(format!(
+            "Port {} answers, but not as an OpenClaw gateway.",
+            endpoint.port
+        ));
+        GatewayHealthState::PortInUse
+    } else if auth_rejected {
+        result.error = Some(if token.is_some() {
+            "The gateway rejected the configured token.".to_string()
+        } else {
+            "The gateway requires a token, but none is configured.".to_string()
+        });
+        GatewayHealthState::Unhealthy
+    } else if status.is_server_error() || auth_status >= 500 {
+        result.error = Some(format!("The gateway answered with HTTP {}.", status));
+        GatewayHealthState::Unhealthy
+    } else {
+        GatewayHealthState::Ready
+    };
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    // Answers one request per `(status, extra header lines)`; hands back the
+    // request heads in order.
+    fn mock_gateway(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (GatewayEndpoint, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock gateway");
+        let endpoint = GatewayEndpoint {
+            host: "127.0.0.1".to_string(),
+            port: listener.local_addr().unwrap().port(),
+            source: "config".to_string(),
+            bind: None,
+        };
+        forget_auth_checks(endpoint.port);
+        let handle = std::thread::spawn(move || {
+            let mut heads = Vec::new();
+            for (status, headers) in responses {
+                let (mut stream, _) = listener.accept().expect("accept");
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).expect("read request");
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..read]);
+                }
+                heads.push(String::from_utf8_lossy(&request).to_string());
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n",
+                    status, headers
+                );
+                stream
+                    .write_all(response.as_bytes())
+                    .expect("write response");
+            }
+            heads
+        });
+        (endpoint, handle)
+    }
+
+    fn run(auth_status: u16, token: Option<&str>) -> (GatewayHealthProbe, String) {
+        let (endpoint, handle) = mock_gateway(vec![(200, ""), (auth_status, "")]);
+        let probe = tauri::async_runtime::block_on(probe(&endpoint, token, false));
+        (probe, handle.join().unwrap().pop().unwrap())
+    }
+
+    #[test]
+    fn accepted_token_is_ready() {
+        let (probe, head) = run(400, Some("secret"));
+        assert!(head.starts_with("POST /tools/invoke "));
+        assert!(head
+            .to_ascii_lowercase()
+            .contains("authorization: bearer secret"));
+        assert_eq!(probe.state, GatewayHealthState::Ready);
+        assert!(probe.is_openclaw);
+        assert_eq!(probe.token_accepted, Some(true));
+    }
+
+    #[test]
+    fn rejected_token_is_unhealthy() {
+        let (probe, _) = run(401, Some("wrong"));
+        assert_eq!(probe.state, GatewayHealthState::Unhealthy);
+        assert_eq!(probe.token_accepted, Some(false));
+
+        let (probe, _) = run(401, None);
+        assert_eq!(probe.state, GatewayHealthState::Unhealthy);
+        assert_eq!(probe.token_accepted, None);
+    }
+
+    #[test]
+    fn server_without_the_gateway_route_is_another_service() {
+        let (probe, _) = run(404, Some("secret"));
+        assert_eq!(probe.state, GatewayHealthState::PortInUse);
+        assert!(!probe.is_openclaw);
+        assert_eq!(probe.token_accepted, None);
+    }
+
+    #[test]
+    fn auth_check_runs_once_per_port_and_reports_the_version() {
+        let (endpoint, handle) = mock_gateway(vec![
+            (200, "X-OpenClaw-Version: 2026.3.2\r\n"),
+            (400, ""),
+            (200, "X-OpenClaw-Version: 2026.3.2\r\n"),
+        ]);
+        for _ in 0..2 {
+            let probe = tauri::async_runtime::block_on(probe(&endpoint, Some("secret"), false));
+            assert_eq!(probe.state, GatewayHealthState::Ready);
+            assert_eq!(probe.version.as_deref(), Some("2026.3.2"));
+        }
+        let heads = handle.join().unwrap();
+        assert!(heads[2].starts_with("GET / "));
+    }
+}
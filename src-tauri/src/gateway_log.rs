@@ -0,0 +1,237 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use tauri::Emitter;
+
+pub const GATEWAY_LOG_EVENT: &str = "gateway-log";
+
+const LOG_FILE_NAME: &str = "desktop-gateway.log";
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+const ROTATED_FILES: usize = 3;
+const MAX_TAIL_LINES: usize = 2000;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayLogLine {
+    pub stream: String,
+    pub line: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayLogTail {
+    pub path: String,
+    pub lines: Vec<String>,
+    pub following: bool,
+}
+
+struct LogState {
+    dir: PathBuf,
+    file: Option<File>,
+    size: u64,
+}
+
+fn log_state() -> &'static OnceLock<Mutex<LogState>> {
+    static STATE: OnceLock<Mutex<LogState>> = OnceLock::new();
+    &STATE
+}
+
+fn app_slot() -> &'static OnceLock<tauri::AppHandle> {
+    static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+    &APP
+}
+
+static FOLLOWING: AtomicBool = AtomicBool::new(false);
+
+pub fn init(app: tauri::AppHandle, dir: PathBuf) {
+    let _ = app_slot().set(app);
+    let _ = log_state().set(Mutex::new(LogState {
+        dir,
+        file: None,
+        size: 0,
+    }));
+}
+
+// Moves logging to another state dir, e.g. after a profile switch.
+pub fn set_dir(dir: PathBuf) {
+    if let Some(state) = log_state().get() {
+        if let Ok(mut state) = state.lock() {
+            state.dir = dir;
+            state.file = None;
+            state.size = 0;
+        }
+    }
+}
+
+fn log_path(dir: &Path, generation: usize) -> PathBuf {
+    if generation == 0 {
+        dir.join(LOG_FILE_NAME)
+    } else {
+        dir.join(format!("{}.{}", LOG_FILE_NAME, generation))
+    }
+}
+
+// UTC `YYYY-MM-DDTHH:MM:SS.mmmZ` without pulling in a date crate.
+fn timestamp() -> String {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or(0);
+    let (days, day_ms) = (millis.div_euclid(86_400_000), millis.rem_euclid(86_400_000));
+
+    // Civil-from-days (Howard Hinnant), valid for the proleptic Gregorian calendar.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        day_ms / 3_600_000,
+        day_ms / 60_000 % 60,
+        day_ms / 1000 % 60,
+        day_ms % 1000
+    )
+}
+
+impl LogState {
+    fn open(&mut self) -> Result<&mut File, String> {
+        if self.file.is_none() {
+            fs::create_dir_all(&self.dir).map_err(|err| {
+                format!(
+                    "Failed to create log dir {}: {}",
+                    self.dir.to_string_lossy(),
+                    err
+                )
+            })?;
+            let path = log_path(&self.dir, 0);
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|err| format!("Failed to open {}: {}", path.to_string_lossy(), err))?;
+            self.size = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+            self.file = Some(file);
+        }
+        self.file
+            .as_mut()
+            .ok_or_else(|| "Gateway log is not open".to_string())
+    }
+
+    fn rotate(&mut self) {
+        self.file = None;
+        let _ = fs::remove_file(log_path(&self.dir, ROTATED_FILES));
+        for generation in (0..ROTATED_FILES).rev() {
+            let _ = fs::rename(
+                log_path(&self.dir, generation),
+                log_path(&self.dir, generation + 1),
+            );
+        }
+        self.size = 0;
+    }
+
+    fn append(&mut self, entry: &str) -> Result<(), String> {
+        let file = self.open()?;
+        file.write_all(entry.as_bytes())
+            .map_err(|err| format!("Failed to write gateway log: {}", err))?;
+        self.size += entry.len() as u64;
+        if self.size >= MAX_LOG_BYTES {
+            self.rotate();
+        }
+        Ok(())
+    }
+}
+
+fn record(stream: &str, raw: &str) {
+    let line = crate::strip_ansi_and_controls(raw).trim_end().to_string();
+    if line.is_empty() {
+        return;
+    }
+    if let Some(state) = log_state().get() {
+        if let Ok(mut state) = state.lock() {
+            let _ = state.append(&format!("{} [{}] {}\n", timestamp(), stream, line));
+        }
+    }
+    if FOLLOWING.load(Ordering::Relaxed) {
+        if let Some(app) = app_slot().get() {
+            let _ = app.emit(
+                GATEWAY_LOG_EVENT,
+                GatewayLogLine {
+                    stream: stream.to_string(),
+                    line,
+                },
+            );
+        }
+    }
+}
+
+// Copies one child stream into the log until the process closes it.
+pub fn capture(stream: &'static str, source: impl Read + Send + 'static) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(source);
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_until(b'\n', &mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => record(stream, &String::from_utf8_lossy(&buffer)),
+            }
+        }
+    });
+}
+
+pub fn note(message: &str) {
+    record("desktop", message);
+}
+
+// Last `lines` lines across the current and rotated files, oldest first.
+// `follow` turns live `gateway-log` events on or off.
+pub fn tail(lines: usize, follow: bool) -> Result<GatewayLogTail, String> {
+    let state = log_state()
+        .get()
+        .ok_or_else(|| "Gateway log is not initialized".to_string())?;
+    let dir = state
+        .lock()
+        .map_err(|_| "Gateway log lock poisoned".to_string())?
+        .dir
+        .clone();
+
+    let wanted = lines.min(MAX_TAIL_LINES);
+    let mut collected: Vec<String> = Vec::new();
+    for generation in 0..=ROTATED_FILES {
+        if collected.len() >= wanted {
+            break;
+        }
+        let Ok(bytes) = fs::read(log_path(&dir, generation)) else {
+            continue;
+        };
+        let text = String::from_utf8_lossy(&bytes);
+        let mut older: Vec<String> = text
+            .lines()
+            .rev()
+            .take(wanted - collected.len())
+            .map(str::to_string)
+            .collect();
+        older.reverse();
+        older.append(&mut collected);
+        collected = older;
+    }
+
+    FOLLOWING.store(follow, Ordering::Relaxed);
+    Ok(GatewayLogTail {
+        path: log_path(&dir, 0).to_string_lossy().to_string(),
+        lines: collected,
+        following: follow,
+    })
+}
@@ -0,0 +1,425 @@
+use crate::gateway_endpoint::GatewayEndpoint;
+use crate::gateway_log;
+use crate::util::now_millis;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+pub const GATEWAY_UP_EVENT: &str = "gateway-up";
+pub const GATEWAY_DOWN_EVENT: &str = "gateway-down";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const INITIAL_BACKOFF_MS: i64 = 1_000;
+const MAX_BACKOFF_MS: i64 = 30_000;
+// Consecutive crashes before the supervisor stops restarting.
+const MAX_CRASH_RESTARTS: u32 = 5;
+// A run that lasted this long counts as healthy and resets the crash streak.
+const STABLE_UPTIME_MS: i64 = 60_000;
+const MAX_EXIT_HISTORY: usize = 20;
+// How long a stopped gateway gets to exit on its own before it is killed.
+const STOP_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayExitRecord {
+    pub pid: u32,
+    pub code: Option<i32>,
+    pub signal: Option<i32>,
+    pub started_at_ms: i64,
+    pub exited_at_ms: i64,
+    pub uptime_ms: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewaySupervisorStatus {
+    pub running: bool,
+    pub pid: Option<u32>,
+    pub port: Option<u16>,
+    pub supervised: bool,
+    pub crash_streak: u32,
+    pub total_restarts: u32,
+    pub gave_up: bool,
+    pub started_at_ms: Option<i64>,
+    pub next_restart_at_ms: Option<i64>,
+    pub exits: Vec<GatewayExitRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayStopResult {
+    // False when the app had no gateway of its own running.
+    pub stopped: bool,
+    pub pid: Option<u32>,
+    // The gateway ignored the graceful signal and had to be killed.
+    pub forced: bool,
+    pub status: GatewaySupervisorStatus,
+}
+
+#[derive(Default)]
+struct SupervisorState {
+    binary: Option<String>,
+    port: Option<u16>,
+    bind: Option<String>,
+    child: Option<Child>,
+    started_at_ms: Option<i64>,
+    // The app wants a gateway running; cleared when it is stopped on purpose.
+    supervised: bool,
+    // A stop is waiting for the child, which has been taken out of `child`.
+    stopping: bool,
+    crash_streak: u32,
+    total_restarts: u32,
+    gave_up: bool,
+    next_restart_at_ms: Option<i64>,
+    exits: VecDeque<GatewayExitRecord>,
+}
+
+impl SupervisorState {
+    fn status(&self) -> GatewaySupervisorStatus {
+        GatewaySupervisorStatus {
+            running: self.child.is_some(),
+            pid: self.child.as_ref().map(Child::id),
+            port: self.port,
+            supervised: self.supervised,
+            crash_streak: self.crash_streak,
+            total_restarts: self.total_restarts,
+            gave_up: self.gave_up,
+            started_at_ms: self.started_at_ms,
+            next_restart_at_ms: self.next_restart_at_ms,
+            exits: self.exits.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+fn state() -> &'static Mutex<SupervisorState> {
+    static STATE: OnceLock<Mutex<SupervisorState>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(SupervisorState::default()))
+}
+
+fn lock_state() -> Result<MutexGuard<'static, SupervisorState>, String> {
+    state()
+        .lock()
+        .map_err(|_| "Failed to lock gateway process state".to_string())
+}
+
+fn app_slot() -> &'static OnceLock<tauri::AppHandle> {
+    static APP: OnceLock<tauri::AppHandle> = OnceLock::new();
+    &APP
+}
+
+fn emit(event: &str, status: GatewaySupervisorStatus) {
+    if let Some(app) = app_slot().get() {
+        let _ = app.emit(event, status);
+    }
+}
+
+fn exit_signal(status: &ExitStatus) -> Option<i32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = status;
+        None
+    }
+}
+
+// On Windows the child is the `openclaw.cmd` wrapper, so the whole process
+// tree is signalled; killing the wrapper alone would orphan node.
+#[cfg(windows)]
+fn taskkill(pid: u32, force: bool) -> bool {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+    let mut command = Command::new("taskkill");
+    if force {
+        command.arg("/F");
+    }
+    command
+        .arg("/T")
+        .arg("/PID")
+        .arg(pid.to_string())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+// Asks the gateway to shut down cleanly. Returns false where there is no
+// graceful signal to send, so the caller can kill right away.
+fn request_graceful_exit(child: &Child) -> bool {
+    #[cfg(unix)]
+    {
+        let Ok(pid) = libc::pid_t::try_from(child.id()) else {
+            return false;
+        };
+        // SAFETY: kill(2) has no memory-safety preconditions; the pid belongs
+        // to a child that has not been reaped yet.
+        unsafe { libc::kill(pid, libc::SIGTERM) == 0 }
+    }
+    #[cfg(windows)]
+    {
+        taskkill(child.id(), false)
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = child;
+        false
+    }
+}
+
+fn force_kill(child: &mut Child) {
+    #[cfg(windows)]
+    {
+        if taskkill(child.id(), true) {
+            return;
+        }
+    }
+    let _ = child.kill();
+}
+
+// Registers the app handle for events and starts the monitor thread.
+pub fn init(app: tauri::AppHandle) {
+    if app_slot().set(app).is_err() {
+        return;
+    }
+    std::thread::spawn(|| loop {
+        std::thread::sleep(POLL_INTERVAL);
+        monitor_tick();
+    });
+}
+
+fn spawn_child(state: &mut SupervisorState) -> Result<(), String> {
+    let binary = state
+        .binary
+        .clone()
+        .ok_or_else(|| "Gateway binary is not known yet".to_string())?;
+    let port = state
+        .port
+        .ok_or_else(|| "Gateway port is not known yet".to_string())?;
+    let mut command = Command::new(&binary);
+    crate::profiles::apply_env(&mut command)
+        .arg("gateway")
+        .arg("run")
+        .arg("--allow-unconfigured")
+        .arg("--port")
+        .arg(port.to_string());
+    if let Some(bind) = &state.bind {
+        command.arg("--bind").arg(bind);
+    }
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to run `openclaw gateway run`: {}", err))?;
+
+    gateway_log::note(&format!(
+        "Started gateway (pid {}) on port {}",
+        child.id(),
+        port
+    ));
+    if let Some(stdout) = child.stdout.take() {
+        gateway_log::capture("stdout", stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        gateway_log::capture("stderr", stderr);
+    }
+
+    state.child = Some(child);
+    state.started_at_ms = Some(now_millis());
+    state.next_restart_at_ms = None;
+    emit(GATEWAY_UP_EVENT, state.status());
+    Ok(())
+}
+
+// Records the exit of the current child and schedules a restart with
+// exponential backoff, unless the crash streak is exhausted.
+fn handle_exit(state: &mut SupervisorState, exit: ExitStatus) {
+    let Some(child) = state.child.take() else {
+        return;
+    };
+    let started_at_ms = state.started_at_ms.take();
+    record_exit(state, child.id(), started_at_ms, exit);
+}
+
+fn record_exit(
+    state: &mut SupervisorState,
+    pid: u32,
+    started_at_ms: Option<i64>,
+    exit: ExitStatus,
+) {
+    let exited_at_ms = now_millis();
+    let started_at_ms = started_at_ms.unwrap_or(exited_at_ms);
+    let uptime_ms = (exited_at_ms - started_at_ms).max(0);
+    state.exits.push_back(GatewayExitRecord {
+        pid,
+        code: exit.code(),
+        signal: exit_signal(&exit),
+        started_at_ms,
+        exited_at_ms,
+        uptime_ms,
+    });
+    while state.exits.len() > MAX_EXIT_HISTORY {
+        state.exits.pop_front();
+    }
+    gateway_log::note(&format!("Gateway (pid {}) exited: {}", pid, exit));
+
+    if state.supervised {
+        if uptime_ms >= STABLE_UPTIME_MS {
+            state.crash_streak = 0;
+        }
+        record_crash(state, exited_at_ms);
+    }
+    emit(GATEWAY_DOWN_EVENT, state.status());
+}
+
+fn record_crash(state: &mut SupervisorState, at_ms: i64) {
+    state.crash_streak += 1;
+    if state.crash_streak > MAX_CRASH_RESTARTS {
+        state.gave_up = true;
+        state.next_restart_at_ms = None;
+    } else {
+        let backoff_ms = (INITIAL_BACKOFF_MS << (state.crash_streak - 1)).min(MAX_BACKOFF_MS);
+        state.next_restart_at_ms = Some(at_ms + backoff_ms);
+    }
+}
+
+fn reap_exited(state: &mut SupervisorState) {
+    if let Some(exit) = state
+        .child
+        .as_mut()
+        .and_then(|child| child.try_wait().ok().flatten())
+    {
+        handle_exit(state, exit);
+    }
+}
+
+fn monitor_tick() {
+    let Ok(mut state) = state().lock() else {
+        return;
+    };
+
+    reap_exited(&mut state);
+
+    let restart_due = state.child.is_none()
+        && state.supervised
+        && !state.gave_up
+        && state
+            .next_restart_at_ms
+            .is_some_and(|due| now_millis() >= due);
+    if restart_due {
+        state.total_restarts += 1;
+        if spawn_child(&mut state).is_err() {
+            // A failed spawn counts as another crash so the streak cap still applies.
+            record_crash(&mut state, now_millis());
+        }
+    }
+}
+
+// Starts a supervised gateway unless one is already running. Returns whether
+// a new process was spawned. An explicit start clears a previous give-up.
+// A running gateway keeps its port and bind until it is restarted.
+pub fn ensure_running(binary: &str, endpoint: &GatewayEndpoint) -> Result<bool, String> {
+    let mut state = lock_state()?;
+    reap_exited(&mut state);
+
+    if state.stopping {
+        return Err("The gateway is still stopping; try again in a moment.".to_string());
+    }
+    state.binary = Some(binary.to_string());
+    state.supervised = true;
+    if state.child.is_some() {
+        return Ok(false);
+    }
+
+    state.port = Some(endpoint.port);
+    state.bind = endpoint.bind.clone();
+    state.gave_up = false;
+    state.crash_streak = 0;
+    spawn_child(&mut state)?;
+    Ok(true)
+}
+
+pub fn is_running() -> bool {
+    let Ok(mut state) = state().lock() else {
+        return false;
+    };
+    reap_exited(&mut state);
+    state.child.is_some() || state.stopping
+}
+
+pub fn status() -> Result<GatewaySupervisorStatus, String> {
+    Ok(lock_state()?.status())
+}
+
+// Stops the gateway this app spawned and turns supervision off. A gateway run
+// by the OpenClaw daemon is never owned here, so it is left alone. The state
+// lock is released while waiting for the process, so status reads and the
+// monitor are not blocked for the grace period.
+pub fn stop() -> Result<GatewayStopResult, String> {
+    let (mut child, started_at_ms) = {
+        let mut state = lock_state()?;
+        reap_exited(&mut state);
+        state.supervised = false;
+        state.next_restart_at_ms = None;
+
+        let Some(child) = state.child.take() else {
+            return Ok(GatewayStopResult {
+                stopped: false,
+                pid: None,
+                forced: false,
+                status: state.status(),
+            });
+        };
+        state.stopping = true;
+        (child, state.started_at_ms.take())
+    };
+    let pid = child.id();
+    gateway_log::note(&format!("Stopping gateway (pid {})", pid));
+
+    let mut forced = !request_graceful_exit(&child);
+    let deadline = Instant::now() + STOP_GRACE_PERIOD;
+    let exit = loop {
+        match child.try_wait() {
+            Ok(Some(exit)) => break Some(exit),
+            Ok(None) if !forced && Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            _ => {
+                forced = true;
+                force_kill(&mut child);
+                break child.wait().ok();
+            }
+        }
+    };
+
+    let mut state = state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    state.stopping = false;
+    match exit {
+        Some(exit) => record_exit(&mut state, pid, started_at_ms, exit),
+        None => emit(GATEWAY_DOWN_EVENT, state.status()),
+    }
+    Ok(GatewayStopResult {
+        stopped: true,
+        pid: Some(pid),
+        forced,
+        status: state.status(),
+    })
+}
+
+pub fn restart(
+    binary: &str,
+    endpoint: &GatewayEndpoint,
+) -> Result<GatewaySupervisorStatus, String> {
+    stop()?;
+    ensure_running(binary, endpoint)?;
+    status()
+}
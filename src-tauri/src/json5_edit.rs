@@ -0,0 +1,608 @@
+// Format-preserving edits for hand-maintained JSON5 config files.
+//
+// Instead of re-serializing the whole document, the structural difference
+// between the value that was read and the value to be written is turned into
+// targeted text edits (replace a value, insert a member, remove a member).
+// Comments, trailing commas, quoting style and key order of untouched parts
+// survive as written.
+
+use serde_json::{Map, Value};
+
+struct Member {
+    key: String,
+    key_start: usize,
+    value: Node,
+    comma: Option<usize>,
+}
+
+enum Node {
+    Object {
+        start: usize,
+        end: usize,
+        members: Vec<Member>,
+    },
+    Other {
+        start: usize,
+        end: usize,
+    },
+}
+
+impl Node {
+    fn start(&self) -> usize {
+        match self {
+            Node::Object { start, .. } | Node::Other { start, .. } => *start,
+        }
+    }
+
+    fn end(&self) -> usize {
+        match self {
+            Node::Object { end, .. } | Node::Other { end, .. } => *end,
+        }
+    }
+}
+
+struct Scanner<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            bytes: text.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{} at byte {}", message, self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), String> {
+        loop {
+            let rest = &self.text[self.pos..];
+            let trimmed =
+                rest.trim_start_matches(|ch: char| ch.is_whitespace() || ch == '\u{feff}');
+            self.pos += rest.len() - trimmed.len();
+            if trimmed.starts_with("//") {
+                self.pos += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else if let Some(comment) = trimmed.strip_prefix("/*") {
+                let close = comment
+                    .find("*/")
+                    .ok_or_else(|| self.error("Unterminated block comment"))?;
+                self.pos += close + 4;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_document(&mut self) -> Result<Node, String> {
+        self.skip_trivia()?;
+        let node = self.parse_value()?;
+        self.skip_trivia()?;
+        if self.pos != self.bytes.len() {
+            return Err(self.error("Unexpected trailing content"));
+        }
+        Ok(node)
+    }
+
+    fn parse_value(&mut self) -> Result<Node, String> {
+        let start = self.pos;
+        match self.peek() {
+            Some(b'{') => self.parse_object(),
+            Some(b'[') => {
+                self.pos += 1;
+                loop {
+                    self.skip_trivia()?;
+                    match self.peek() {
+                        Some(b']') => {
+                            self.pos += 1;
+                            break;
+                        }
+                        Some(b',') => self.pos += 1,
+                        Some(_) => {
+                            self.parse_value()?;
+                        }
+                        None => return Err(self.error("Unterminated array")),
+                    }
+                }
+                Ok(Node::Other {
+                    start,
+                    end: self.pos,
+                })
+            }
+            Some(b'"') | Some(b'\'') => {
+                self.parse_string()?;
+                Ok(Node::Other {
+                    start,
+                    end: self.pos,
+                })
+            }
+            Some(_) => {
+                while let Some(byte) = self.peek() {
+                    if byte.is_ascii_whitespace() || matches!(byte, b',' | b'}' | b']' | b'/') {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                if self.pos == start {
+                    return Err(self.error("Expected a value"));
+                }
+                Ok(Node::Other {
+                    start,
+                    end: self.pos,
+                })
+            }
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<(), String> {
+        let quote = self.peek().ok_or_else(|| self.error("Expected a string"))?;
+        self.pos += 1;
+        while let Some(byte) = self.peek() {
+            self.pos += 1;
+            if byte == b'\\' {
+                self.pos += 1;
+            } else if byte == quote {
+                return Ok(());
+            }
+        }
+        Err(self.error("Unterminated string"))
+    }
+
+    fn parse_key(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        match self.peek() {
+            Some(b'"') | Some(b'\'') => {
+                self.parse_string()?;
+                let raw = &self.text[start..self.pos];
+                json5::from_str::<String>(raw).map_err(|err| self.error(&err.to_string()))
+            }
+            Some(_) => {
+                while let Some(byte) = self.peek() {
+                    if byte.is_ascii_whitespace() || byte == b':' || byte == b'/' {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+                if self.pos == start {
+                    return Err(self.error("Expected an object key"));
+                }
+                Ok(self.text[start..self.pos].to_string())
+            }
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Node, String> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut members: Vec<Member> = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            match self.peek() {
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Node::Object {
+                        start,
+                        end: self.pos,
+                        members,
+                    });
+                }
+                Some(_) => {
+                    if members.last().is_some_and(|member| member.comma.is_none()) {
+                        return Err(self.error("Expected ',' between object members"));
+                    }
+                    let key_start = self.pos;
+                    let key = self.parse_key()?;
+                    self.skip_trivia()?;
+                    if self.peek() != Some(b':') {
+                        return Err(self.error("Expected ':' after object key"));
+                    }
+                    self.pos += 1;
+                    self.skip_trivia()?;
+                    let value = self.parse_value()?;
+                    self.skip_trivia()?;
+                    let comma = if self.peek() == Some(b',') {
+                        self.pos += 1;
+                        Some(self.pos - 1)
+                    } else {
+                        None
+                    };
+                    members.push(Member {
+                        key,
+                        key_start,
+                        value,
+                        comma,
+                    });
+                }
+                None => return Err(self.error("Unterminated object")),
+            }
+        }
+    }
+}
+
+struct Edit {
+    start: usize,
+    end: usize,
+    replacement: String,
+}
+
+fn line_indent(text: &str, pos: usize) -> &str {
+    let line_start = text[..pos].rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    let line = &text[line_start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+fn starts_own_line(text: &str, pos: usize) -> bool {
+    let line_start = text[..pos].rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+    text[line_start..pos].trim().is_empty()
+}
+
+fn render_value(value: &Value, indent: &str) -> Result<String, String> {
+    let pretty = serde_json::to_string_pretty(value)
+        .map_err(|err| format!("Failed to serialize value: {}", err))?;
+    Ok(pretty.replace('\n', &format!("\n{}", indent)))
+}
+
+fn render_member(key: &str, value: &Value, indent: &str) -> Result<String, String> {
+    let key =
+        serde_json::to_string(key).map_err(|err| format!("Failed to serialize key: {}", err))?;
+    Ok(format!("{}: {}", key, render_value(value, indent)?))
+}
+
+fn diff_object(
+    text: &str,
+    node: &Node,
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    edits: &mut Vec<Edit>,
+) -> Result<(), String> {
+    let Node::Object {
+        start,
+        end,
+        members,
+    } = node
+    else {
+        return Err("Expected an object node".to_string());
+    };
+    let find_member = |key: &str| members.iter().rposition(|member| member.key == key);
+
+    let mut removed: Vec<usize> = Vec::new();
+    for key in old.keys() {
+        if !new.contains_key(key) {
+            removed.push(find_member(key).ok_or_else(|| format!("Member {} not found", key))?);
+        }
+    }
+
+    let mut inserted: Vec<(&String, &Value)> = Vec::new();
+    for (key, new_value) in new {
+        match old.get(key) {
+            Some(old_value) if old_value == new_value => {}
+            Some(old_value) => {
+                let index = find_member(key).ok_or_else(|| format!("Member {} not found", key))?;
+                let member_value = &members[index].value;
+                match (old_value, new_value, member_value) {
+                    (Value::Object(old_obj), Value::Object(new_obj), Node::Object { .. }) => {
+                        diff_object(text, member_value, old_obj, new_obj, edits)?;
+                    }
+                    _ => edits.push(Edit {
+                        start: member_value.start(),
+                        end: member_value.end(),
+                        replacement: render_value(
+                            new_value,
+                            line_indent(text, members[index].key_start),
+                        )?,
+                    }),
+                }
+            }
+            None => inserted.push((key, new_value)),
+        }
+    }
+
+    let last_index = members.len().checked_sub(1);
+    if !inserted.is_empty() && last_index.is_some_and(|index| removed.contains(&index)) {
+        // Removing the anchor member and appending in the same object: re-render just this object.
+        edits.retain(|edit| edit.end <= *start || edit.start >= *end);
+        edits.push(Edit {
+            start: *start,
+            end: *end,
+            replacement: render_value(&Value::Object(new.clone()), line_indent(text, *start))?,
+        });
+        return Ok(());
+    }
+
+    // Remove contiguous runs of members as one edit each, so the separators
+    // between neighbouring removed members are never edited twice.
+    removed.sort_unstable();
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for &index in &removed {
+        match runs.last_mut() {
+            Some((_, last)) if *last + 1 == index => *last = index,
+            _ => runs.push((index, index)),
+        }
+    }
+    for (first, last) in runs {
+        let prev_end = if first == 0 {
+            start + 1
+        } else {
+            members[first - 1]
+                .comma
+                .map(|comma| comma + 1)
+                .unwrap_or(members[first - 1].value.end())
+        };
+        // Keep whatever trails the previous member on its line (usually a comment).
+        let remove_start = text[prev_end..members[first].key_start]
+            .find('\n')
+            .map(|offset| prev_end + offset)
+            .unwrap_or(prev_end);
+        let remove_end = members[last]
+            .comma
+            .map(|comma| comma + 1)
+            .unwrap_or(members[last].value.end());
+        if first > 0 && Some(last) == last_index && members[last].comma.is_none() {
+            if let Some(prev_comma) = members[first - 1].comma {
+                edits.push(Edit {
+                    start: prev_comma,
+                    end: prev_comma + 1,
+                    replacement: String::new(),
+                });
+            }
+        }
+        edits.push(Edit {
+            start: remove_start,
+            end: remove_end,
+            replacement: String::new(),
+        });
+    }
+
+    if inserted.is_empty() {
+        return Ok(());
+    }
+
+    match members.last() {
+        Some(last) => {
+            let multiline = starts_own_line(text, last.key_start);
+            let indent = line_indent(text, last.key_start).to_string();
+            let separator = if multiline {
+                format!("\n{}", indent)
+            } else {
+                " ".to_string()
+            };
+            let rendered = inserted
+                .iter()
+                .map(|(key, value)| render_member(key, value, &indent))
+                .collect::<Result<Vec<_>, _>>()?;
+            let (at, replacement) = match last.comma {
+                Some(comma) => (
+                    comma + 1,
+                    rendered
+                        .iter()
+                        .map(|member| format!("{}{},", separator, member))
+                        .collect::<String>(),
+                ),
+                None => (
+                    last.value.end(),
+                    rendered
+                        .iter()
+                        .map(|member| format!(",{}{}", separator, member))
+                        .collect::<String>(),
+                ),
+            };
+            edits.push(Edit {
+                start: at,
+                end: at,
+                replacement,
+            });
+        }
+        None => {
+            let outer_indent = line_indent(text, *start).to_string();
+            let indent = format!("{}  ", outer_indent);
+            let rendered = inserted
+                .iter()
+                .map(|(key, value)| render_member(key, value, &indent))
+                .collect::<Result<Vec<_>, _>>()?;
+            let inner = text[start + 1..end - 1].trim_end();
+            edits.push(Edit {
+                start: start + 1,
+                end: end - 1,
+                replacement: format!(
+                    "{}\n{}{}\n{}",
+                    inner,
+                    indent,
+                    rendered.join(&format!(",\n{}", indent)),
+                    outer_indent
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+// Rewrites `text` (whose parsed value is `old`) so that it parses to `new`,
+// touching only the members that changed.
+pub fn apply_value_diff(text: &str, old: &Value, new: &Value) -> Result<String, String> {
+    let (Value::Object(old_obj), Value::Object(new_obj)) = (old, new) else {
+        return Err("Only object documents can be edited in place".to_string());
+    };
+
+    let root = Scanner::new(text).parse_document()?;
+    let mut edits = Vec::new();
+    diff_object(text, &root, old_obj, new_obj, &mut edits)?;
+
+    edits.sort_by_key(|edit| std::cmp::Reverse(edit.start));
+    let mut output = text.to_string();
+    let mut previous_start = usize::MAX;
+    for edit in edits {
+        if edit.end > previous_start {
+            return Err("Overlapping edits".to_string());
+        }
+        output.replace_range(edit.start..edit.end, &edit.replacement);
+        previous_start = edit.start;
+    }
+
+    let reparsed = json5::from_str::<Value>(&output)
+        .map_err(|err| format!("Edited config no longer parses: {}", err))?;
+    if &reparsed != new {
+        return Err("Edited config does not match the intended value".to_string());
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edit(text: &str, change: impl FnOnce(&mut Value)) -> String {
+        let old = json5::from_str::<Value>(text).unwrap();
+        let mut new = old.clone();
+        change(&mut new);
+        apply_value_diff(text, &old, &new).unwrap()
+    }
+
+    #[test]
+    fn replacing_a_value_keeps_comments_and_trailing_commas() {
+        let text = r#"{
+  // gateway settings
+  gateway: {
+    port: 18789, // default port
+    mode: 'local',
+  },
+  /* models */
+  agents: { defaults: { model: "openai/gpt-5" } },
+}
+"#;
+        let output = edit(text, |value| {
+            value["gateway"]["port"] = json!(19001);
+        });
+        assert_eq!(output, text.replace("18789", "19001"));
+    }
+
+    #[test]
+    fn nested_replace_touches_only_the_changed_member() {
+        let text = r#"{
+  agents: {
+    defaults: {
+      // primary model
+      model: { primary: "openai/gpt-5", fallbacks: [] },
+      workspace: "~/work",
+    },
+  },
+}"#;
+        let output = edit(text, |value| {
+            value["agents"]["defaults"]["model"]["primary"] = json!("anthropic/claude-sonnet-4-5");
+        });
+        assert_eq!(
+            output,
+            text.replace("\"openai/gpt-5\"", "\"anthropic/claude-sonnet-4-5\"")
+        );
+    }
+
+    #[test]
+    fn inserting_members_follows_the_existing_layout() {
+        let text = "{\n  // keep me\n  gateway: { port: 18789 },\n}\n";
+        let output = edit(text, |value| {
+            value["auth"] = json!({ "order": { "openai": ["openai:default"] } });
+            value["gateway"]["bind"] = json!("loopback");
+        });
+        assert!(output.contains("// keep me"));
+        assert!(output.contains("gateway: { port: 18789, \"bind\": \"loopback\" },"));
+        assert!(output.contains("\n  \"auth\": {\n    \"order\": {"));
+        assert_eq!(
+            json5::from_str::<Value>(&output).unwrap(),
+            json!({
+                "gateway": { "port": 18789, "bind": "loopback" },
+                "auth": { "order": { "openai": ["openai:default"] } }
+            })
+        );
+    }
+
+    #[test]
+    fn inserting_into_an_empty_object() {
+        let text = "{\n  auth: {},\n}";
+        let output = edit(text, |value| {
+            value["auth"]["profiles"] = json!({});
+        });
+        assert_eq!(output, "{\n  auth: {\n    \"profiles\": {}\n  },\n}");
+    }
+
+    #[test]
+    fn removing_members_keeps_neighbouring_comments() {
+        let text = r#"{
+  a: 1, // about a
+  // about b
+  b: 2,
+  c: 3,
+}"#;
+        let output = edit(text, |value| {
+            value.as_object_mut().unwrap().remove("b");
+        });
+        assert_eq!(output, "{\n  a: 1, // about a\n  c: 3,\n}");
+    }
+
+    #[test]
+    fn removing_the_last_two_members() {
+        let text = "{\n  a: 1,\n  b: 2,\n  c: 3\n}";
+        let output = edit(text, |value| {
+            let object = value.as_object_mut().unwrap();
+            object.remove("b");
+            object.remove("c");
+        });
+        assert_eq!(output, "{\n  a: 1\n}");
+    }
+
+    #[test]
+    fn removing_non_adjacent_members_with_trailing_commas() {
+        let text = "{ 'a': 1, b: 2, \"c\": 3, d: 4, }";
+        let output = edit(text, |value| {
+            let object = value.as_object_mut().unwrap();
+            object.remove("a");
+            object.remove("c");
+            object.remove("d");
+        });
+        assert_eq!(
+            json5::from_str::<Value>(&output).unwrap(),
+            json!({ "b": 2 })
+        );
+        assert!(output.contains("b: 2,"));
+    }
+
+    #[test]
+    fn replacing_the_last_member_while_inserting() {
+        let text = "{\n  // header\n  a: 1,\n  b: 2\n}";
+        let output = edit(text, |value| {
+            let object = value.as_object_mut().unwrap();
+            object.remove("b");
+            object.insert("c".to_string(), json!(3));
+        });
+        assert_eq!(
+            json5::from_str::<Value>(&output).unwrap(),
+            json!({ "a": 1, "c": 3 })
+        );
+    }
+
+    #[test]
+    fn strings_that_look_like_comments_are_not_comments() {
+        let text =
+            "{\n  url: \"http://127.0.0.1:18789\", // local\n  note: '/* not a comment */',\n}";
+        let output = edit(text, |value| {
+            value["url"] = json!("http://127.0.0.1:19001");
+        });
+        assert_eq!(output, text.replace("18789", "19001"));
+    }
+
+    #[test]
+    fn non_object_documents_are_rejected() {
+        assert!(apply_value_diff("[1]", &json!([1]), &json!([2])).is_err());
+        assert!(apply_value_diff("{ a: 1 ", &json!({ "a": 1 }), &json!({ "a": 2 })).is_err());
+    }
+}
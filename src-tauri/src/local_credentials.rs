@@ -0,0 +1,673 @@
+use crate::util::now_millis;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const CLAUDE_KEYCHAIN_SERVICE: &str = "Claude Code-credentials";
+// Same id the OpenClaw CLI uses for credentials taken over from Claude Code.
+const CLAUDE_CLI_PROFILE_ID: &str = "anthropic:claude-cli";
+const OPENAI_CODEX_DEFAULT_MODEL: &str = "openai-codex/gpt-5.3-codex";
+const ANTHROPIC_CLAUDE_DEFAULT_MODEL: &str = "anthropic/claude-sonnet-4-5";
+const GEMINI_CLI_DEFAULT_MODEL: &str = "google-gemini-cli/gemini-2.5-pro";
+// Used when a login carries no expiry of its own.
+const ASSUMED_TOKEN_LIFETIME_MS: i64 = 60 * 60 * 1000;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalOAuthToolStatus {
+    pub id: String,
+    pub label: String,
+    pub provider_id: String,
+    pub cli_found: bool,
+    pub auth_detected: bool,
+    pub source: String,
+    pub detail: Option<String>,
+}
+
+// A login found on this machine, shaped as an OpenClaw OAuth credential.
+pub struct LocalOAuthCredential {
+    pub label: &'static str,
+    pub provider_id: &'static str,
+    pub profile_id: String,
+    pub email: Option<String>,
+    pub expires: i64,
+    pub credential: serde_json::Map<String, Value>,
+}
+
+// The model to make primary after a reuse, and the prefixes of primary models
+// it is allowed to replace.
+pub type DefaultModel = (&'static str, &'static [&'static str]);
+
+// A CLI whose cached login can be reused by OpenClaw.
+pub trait LocalCredentialSource {
+    fn id(&self) -> &'static str;
+    fn label(&self) -> &'static str;
+    fn provider_id(&self) -> &'static str;
+
+    fn detect(&self) -> LocalOAuthToolStatus;
+
+    // The login as the CLI stores it, without interpretation.
+    fn read_credentials(&self) -> Result<Value, String>;
+
+    fn to_openclaw_profile(&self, raw: &Value) -> Result<LocalOAuthCredential, String>;
+
+    fn default_model(&self) -> Option<DefaultModel> {
+        None
+    }
+
+    fn load(&self) -> Result<LocalOAuthCredential, String> {
+        self.to_openclaw_profile(&self.read_credentials()?)
+    }
+
+    fn status(
+        &self,
+        cli_found: bool,
+        auth_detected: bool,
+        source: String,
+        detail: String,
+    ) -> LocalOAuthToolStatus {
+        LocalOAuthToolStatus {
+            id: self.id().to_string(),
+            label: self.label().to_string(),
+            provider_id: self.provider_id().to_string(),
+            cli_found,
+            auth_detected,
+            source,
+            detail: Some(detail),
+        }
+    }
+}
+
+pub fn registry() -> Vec<Box<dyn LocalCredentialSource>> {
+    vec![
+        Box::new(CodexSource),
+        Box::new(ClaudeCodeSource::default()),
+        Box::new(GeminiCliSource),
+    ]
+}
+
+pub fn find(tool_id: &str) -> Option<Box<dyn LocalCredentialSource>> {
+    registry()
+        .into_iter()
+        .find(|source| source.id() == tool_id.trim())
+}
+
+fn read_json(path: &Path, what: &str) -> Result<Value, String> {
+    let raw = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {}", path.to_string_lossy(), err))?;
+    serde_json::from_str::<Value>(&raw).map_err(|err| format!("Invalid {} format: {}", what, err))
+}
+
+fn decode<T: for<'de> Deserialize<'de>>(raw: &Value, what: &str) -> Result<T, String> {
+    serde_json::from_value(raw.clone()).map_err(|err| format!("Invalid {} format: {}", what, err))
+}
+
+fn required_token(value: Option<String>) -> String {
+    value.unwrap_or_default().trim().to_string()
+}
+
+fn oauth_credential(
+    provider_id: &str,
+    access: String,
+    refresh: String,
+    expires: i64,
+) -> serde_json::Map<String, Value> {
+    let mut credential = serde_json::Map::new();
+    credential.insert("type".to_string(), serde_json::json!("oauth"));
+    credential.insert("provider".to_string(), serde_json::json!(provider_id));
+    credential.insert("access".to_string(), serde_json::json!(access));
+    credential.insert("refresh".to_string(), serde_json::json!(refresh));
+    credential.insert("expires".to_string(), serde_json::json!(expires));
+    credential
+}
+
+#[derive(Deserialize)]
+struct CodexAuthFile {
+    tokens: Option<CodexAuthTokens>,
+}
+
+#[derive(Deserialize)]
+struct CodexAuthTokens {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    account_id: Option<String>,
+    id_token: Option<String>,
+}
+
+pub struct CodexSource;
+
+impl LocalCredentialSource for CodexSource {
+    fn id(&self) -> &'static str {
+        "codex"
+    }
+
+    fn label(&self) -> &'static str {
+        "Codex"
+    }
+
+    fn provider_id(&self) -> &'static str {
+        "openai-codex"
+    }
+
+    fn detect(&self) -> LocalOAuthToolStatus {
+        let detected = self.load().is_ok();
+        self.status(
+            crate::command_exists("codex", &["--version"]),
+            detected,
+            crate::resolve_codex_auth_path()
+                .to_string_lossy()
+                .to_string(),
+            if detected {
+                "Detected local Codex auth tokens.".to_string()
+            } else {
+                "No local Codex auth token detected.".to_string()
+            },
+        )
+    }
+
+    fn read_credentials(&self) -> Result<Value, String> {
+        read_json(&crate::resolve_codex_auth_path(), "Codex auth file")
+    }
+
+    fn to_openclaw_profile(&self, raw: &Value) -> Result<LocalOAuthCredential, String> {
+        let parsed = decode::<CodexAuthFile>(raw, "Codex auth file")?;
+        let tokens = parsed
+            .tokens
+            .ok_or_else(|| "Codex auth tokens field is missing.".to_string())?;
+
+        let access_token = required_token(tokens.access_token);
+        let refresh_token = required_token(tokens.refresh_token);
+        if access_token.is_empty() || refresh_token.is_empty() {
+            return Err("Codex auth file is missing access_token or refresh_token.".to_string());
+        }
+
+        let account_id = tokens
+            .account_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .or_else(|| crate::jwt_openai_account_id(&access_token));
+        let expires = crate::jwt_exp_millis(&access_token)
+            .or_else(|| tokens.id_token.as_deref().and_then(crate::jwt_exp_millis))
+            .unwrap_or_else(|| now_millis() + ASSUMED_TOKEN_LIFETIME_MS);
+        let email = crate::jwt_email(&access_token)
+            .or_else(|| tokens.id_token.as_deref().and_then(crate::jwt_email));
+        let profile_id = email
+            .as_ref()
+            .map(|mail| format!("openai-codex:{}", mail))
+            .unwrap_or_else(|| "openai-codex:default".to_string());
+
+        let mut credential =
+            oauth_credential(self.provider_id(), access_token, refresh_token, expires);
+        if let Some(value) = &account_id {
+            credential.insert("accountId".to_string(), serde_json::json!(value));
+        }
+        if let Some(value) = &email {
+            credential.insert("email".to_string(), serde_json::json!(value));
+        }
+        Ok(LocalOAuthCredential {
+            label: self.label(),
+            provider_id: self.provider_id(),
+            profile_id,
+            email,
+            expires,
+            credential,
+        })
+    }
+
+    fn default_model(&self) -> Option<DefaultModel> {
+        Some((OPENAI_CODEX_DEFAULT_MODEL, &["anthropic/", "openai/"]))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClaudeCredentialsFile {
+    claude_ai_oauth: Option<ClaudeOAuth>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClaudeOAuth {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    expires_at: Option<i64>,
+}
+
+#[derive(Default)]
+pub struct ClaudeCodeSource {
+    // Read instead of the default locations; only tests set it.
+    pub credentials_path: Option<PathBuf>,
+}
+
+pub fn resolve_claude_credentials_path() -> PathBuf {
+    if let Some(config_dir) = crate::read_env_path("CLAUDE_CONFIG_DIR") {
+        return config_dir.join(".credentials.json");
+    }
+    if let Some(home) = crate::resolve_user_home() {
+        return home.join(".claude").join(".credentials.json");
+    }
+    PathBuf::from(".claude/.credentials.json")
+}
+
+fn read_claude_keychain_credentials() -> Option<String> {
+    if !cfg!(target_os = "macos") {
+        return None;
+    }
+    let output = Command::new("security")
+        .arg("find-generic-password")
+        .arg("-s")
+        .arg(CLAUDE_KEYCHAIN_SERVICE)
+        .arg("-w")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+impl LocalCredentialSource for ClaudeCodeSource {
+    fn id(&self) -> &'static str {
+        "claude-code"
+    }
+
+    fn label(&self) -> &'static str {
+        "Claude Code"
+    }
+
+    fn provider_id(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn detect(&self) -> LocalOAuthToolStatus {
+        let path = resolve_claude_credentials_path();
+        let file_detected = path.exists();
+        let keychain_detected = !file_detected && read_claude_keychain_credentials().is_some();
+        let cli_found = crate::command_exists("claude", &["--version"])
+            || crate::command_exists("claude-code", &["--version"]);
+        self.status(
+            cli_found,
+            file_detected || keychain_detected,
+            if keychain_detected {
+                format!("macOS Keychain ({})", CLAUDE_KEYCHAIN_SERVICE)
+            } else {
+                path.to_string_lossy().to_string()
+            },
+            if file_detected || keychain_detected {
+                "Detected reusable Claude Code credentials.".to_string()
+            } else {
+                "No reusable Claude Code credentials found.".to_string()
+            },
+        )
+    }
+
+    // An explicit path is read as-is; otherwise the credentials file wins over
+    // the macOS keychain entry, matching what Claude Code itself prefers.
+    fn read_credentials(&self) -> Result<Value, String> {
+        if let Some(path) = &self.credentials_path {
+            return read_json(path, "Claude credentials");
+        }
+        let path = resolve_claude_credentials_path();
+        if path.exists() {
+            return read_json(&path, "Claude credentials");
+        }
+        match read_claude_keychain_credentials() {
+            Some(raw) => serde_json::from_str::<Value>(&raw)
+                .map_err(|err| format!("Invalid Claude credentials format: {}", err)),
+            None => Err(format!(
+                "No Claude Code credentials found at {}.",
+                path.to_string_lossy()
+            )),
+        }
+    }
+
+    fn to_openclaw_profile(&self, raw: &Value) -> Result<LocalOAuthCredential, String> {
+        let parsed = decode::<ClaudeCredentialsFile>(raw, "Claude credentials")?;
+        let oauth = parsed
+            .claude_ai_oauth
+            .ok_or_else(|| "Claude credentials claudeAiOauth field is missing.".to_string())?;
+
+        let access_token = required_token(oauth.access_token);
+        let refresh_token = required_token(oauth.refresh_token);
+        if access_token.is_empty() || refresh_token.is_empty() {
+            return Err("Claude credentials are missing accessToken or refreshToken.".to_string());
+        }
+        let expires = oauth
+            .expires_at
+            .unwrap_or_else(|| now_millis() + ASSUMED_TOKEN_LIFETIME_MS);
+
+        Ok(LocalOAuthCredential {
+            label: self.label(),
+            provider_id: self.provider_id(),
+            profile_id: CLAUDE_CLI_PROFILE_ID.to_string(),
+            email: None,
+            expires,
+            credential: oauth_credential(self.provider_id(), access_token, refresh_token, expires),
+        })
+    }
+
+    fn default_model(&self) -> Option<DefaultModel> {
+        Some((
+            ANTHROPIC_CLAUDE_DEFAULT_MODEL,
+            &["openai/", "openai-codex/"],
+        ))
+    }
+}
+
+#[derive(Deserialize)]
+struct GeminiOAuthCreds {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    id_token: Option<String>,
+    expiry_date: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct GeminiAccounts {
+    active: Option<String>,
+}
+
+pub struct GeminiCliSource;
+
+// Env vars the Gemini CLI reads the Code Assist project from.
+const GEMINI_PROJECT_ENV_VARS: &[&str] = &["GOOGLE_CLOUD_PROJECT", "GOOGLE_CLOUD_PROJECT_ID"];
+
+fn resolve_gemini_dir() -> PathBuf {
+    if let Some(home) = crate::resolve_user_home() {
+        return home.join(".gemini");
+    }
+    PathBuf::from(".gemini")
+}
+
+// The Gemini CLI keeps the signed-in account next to the cached tokens.
+fn read_gemini_active_account(gemini_dir: &Path) -> Option<String> {
+    let raw = fs::read_to_string(gemini_dir.join("google_accounts.json")).ok()?;
+    let accounts = serde_json::from_str::<GeminiAccounts>(&raw).ok()?;
+    accounts
+        .active
+        .map(|email| email.trim().to_string())
+        .filter(|email| !email.is_empty())
+}
+
+fn read_dotenv_value(path: &Path, key: &str) -> Option<String> {
+    let raw = fs::read_to_string(path).ok()?;
+    raw.lines().find_map(|line| {
+        let line = line.trim();
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (name, value) = line.split_once('=')?;
+        if name.trim() != key {
+            return None;
+        }
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+        (!value.is_empty()).then(|| value.to_string())
+    })
+}
+
+// The process env wins over `~/.gemini/.env`, as in the Gemini CLI.
+fn read_gemini_project_id(gemini_dir: &Path) -> Option<String> {
+    GEMINI_PROJECT_ENV_VARS.iter().find_map(|name| {
+        std::env::var(name)
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .or_else(|| read_dotenv_value(&gemini_dir.join(".env"), name))
+    })
+}
+
+impl LocalCredentialSource for GeminiCliSource {
+    fn id(&self) -> &'static str {
+        "gemini-cli"
+    }
+
+    fn label(&self) -> &'static str {
+        "Gemini CLI"
+    }
+
+    fn provider_id(&self) -> &'static str {
+        "google-gemini-cli"
+    }
+
+    fn detect(&self) -> LocalOAuthToolStatus {
+        let cli_found = crate::command_exists("gemini", &["--version"]);
+        let creds_path = resolve_gemini_dir().join("oauth_creds.json");
+        let auth = self.load();
+        let detail = match &auth {
+            Ok(credential) => match &credential.email {
+                Some(email) => format!("Detected cached Gemini CLI login for {}.", email),
+                None => "Detected cached Gemini CLI login.".to_string(),
+            },
+            Err(error) if creds_path.exists() => {
+                format!("Gemini CLI credentials found but unusable: {}", error)
+            }
+            Err(_) if cli_found => "Gemini CLI detected; not logged in.".to_string(),
+            Err(_) => "Gemini CLI is not installed.".to_string(),
+        };
+        self.status(
+            cli_found,
+            auth.is_ok(),
+            creds_path.to_string_lossy().to_string(),
+            detail,
+        )
+    }
+
+    // `{ "creds": <oauth_creds.json>, "account": <active email>, "projectId": ... }`,
+    // since the CLI splits the login over two files and takes the project
+    // from its environment.
+    fn read_credentials(&self) -> Result<Value, String> {
+        let gemini_dir = resolve_gemini_dir();
+        let creds = read_json(
+            &gemini_dir.join("oauth_creds.json"),
+            "Gemini OAuth credentials",
+        )?;
+        Ok(serde_json::json!({
+            "creds": creds,
+            "account": read_gemini_active_account(&gemini_dir),
+            "projectId": read_gemini_project_id(&gemini_dir),
+        }))
+    }
+
+    fn to_openclaw_profile(&self, raw: &Value) -> Result<LocalOAuthCredential, String> {
+        let creds = decode::<GeminiOAuthCreds>(
+            raw.get("creds").unwrap_or(&Value::Null),
+            "Gemini OAuth credentials",
+        )?;
+        let access_token = required_token(creds.access_token);
+        let refresh_token = required_token(creds.refresh_token);
+        if access_token.is_empty() || refresh_token.is_empty() {
+            return Err(
+                "Gemini OAuth credentials are missing access_token or refresh_token.".to_string(),
+            );
+        }
+        // OpenClaw sends every Gemini CLI request on behalf of this project.
+        let project_id = raw
+            .get("projectId")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                "No Google Cloud project found for the Gemini CLI login; set GOOGLE_CLOUD_PROJECT, or log in with `openclaw models auth login --provider google-gemini-cli` to have one resolved.".to_string()
+            })?;
+        let expires = creds
+            .expiry_date
+            .or_else(|| creds.id_token.as_deref().and_then(crate::jwt_exp_millis))
+            .unwrap_or_else(|| now_millis() + ASSUMED_TOKEN_LIFETIME_MS);
+        let email = raw
+            .get("account")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .or_else(|| creds.id_token.as_deref().and_then(crate::jwt_email));
+        let profile_id = email
+            .as_ref()
+            .map(|mail| format!("google-gemini-cli:{}", mail))
+            .unwrap_or_else(|| "google-gemini-cli:default".to_string());
+
+        let mut credential =
+            oauth_credential(self.provider_id(), access_token, refresh_token, expires);
+        if let Some(value) = &email {
+            credential.insert("email".to_string(), serde_json::json!(value));
+        }
+        credential.insert("projectId".to_string(), serde_json::json!(project_id));
+        Ok(LocalOAuthCredential {
+            label: self.label(),
+            provider_id: self.provider_id(),
+            profile_id,
+            email,
+            expires,
+            credential,
+        })
+    }
+
+    fn default_model(&self) -> Option<DefaultModel> {
+        Some((GEMINI_CLI_DEFAULT_MODEL, &["google/"]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_claude_fixture(name: &str, contents: &str) -> Result<LocalOAuthCredential, String> {
+        let dir = std::env::temp_dir().join(format!(
+            "openclaw-claude-credentials-{}-{}",
+            std::process::id(),
+            name
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(".credentials.json");
+        fs::write(&path, contents).unwrap();
+        let source = ClaudeCodeSource {
+            credentials_path: Some(path),
+        };
+        let result = source.load();
+        let _ = fs::remove_dir_all(dir);
+        result
+    }
+
+    #[test]
+    fn claude_credentials_map_to_an_anthropic_oauth_profile() {
+        let credential = load_claude_fixture(
+            "valid",
+            r#"{
+                "claudeAiOauth": {
+                    "accessToken": "sk-ant-oat01-access",
+                    "refreshToken": "sk-ant-ort01-refresh",
+                    "expiresAt": 1767225600000,
+                    "scopes": ["user:inference"],
+                    "subscriptionType": "max"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(credential.label, "Claude Code");
+        assert_eq!(credential.provider_id, "anthropic");
+        assert_eq!(credential.profile_id, "anthropic:claude-cli");
+        assert_eq!(credential.email, None);
+        assert_eq!(credential.expires, 1767225600000);
+        assert_eq!(
+            Value::Object(credential.credential),
+            serde_json::json!({
+                "type": "oauth",
+                "provider": "anthropic",
+                "access": "sk-ant-oat01-access",
+                "refresh": "sk-ant-ort01-refresh",
+                "expires": 1767225600000i64
+            })
+        );
+    }
+
+    #[test]
+    fn claude_credentials_without_oauth_block_are_rejected() {
+        let error = load_claude_fixture("no-oauth", r#"{ "mcpOAuth": {} }"#)
+            .err()
+            .unwrap();
+        assert!(error.contains("claudeAiOauth"), "{}", error);
+    }
+
+    #[test]
+    fn claude_credentials_without_refresh_token_are_rejected() {
+        let error = load_claude_fixture(
+            "no-refresh",
+            r#"{ "claudeAiOauth": { "accessToken": "a", "refreshToken": "  ", "expiresAt": 1 } }"#,
+        )
+        .err()
+        .unwrap();
+        assert!(error.contains("refreshToken"), "{}", error);
+    }
+
+    #[test]
+    fn gemini_login_stores_its_project() {
+        let credential = GeminiCliSource
+            .to_openclaw_profile(&serde_json::json!({
+                "creds": {
+                    "access_token": "ya29.access",
+                    "refresh_token": "1//refresh",
+                    "expiry_date": 1767225600000i64
+                },
+                "account": "dev@example.com",
+                "projectId": "my-project-123"
+            }))
+            .unwrap();
+        assert_eq!(credential.profile_id, "google-gemini-cli:dev@example.com");
+        assert_eq!(
+            credential.credential.get("projectId"),
+            Some(&serde_json::json!("my-project-123"))
+        );
+
+        let error = GeminiCliSource
+            .to_openclaw_profile(&serde_json::json!({
+                "creds": { "access_token": "a", "refresh_token": "r" },
+                "account": null,
+                "projectId": null
+            }))
+            .err()
+            .unwrap();
+        assert!(error.contains("GOOGLE_CLOUD_PROJECT"), "{}", error);
+    }
+
+    #[test]
+    fn gemini_project_is_read_from_the_cli_dotenv() {
+        let dir = std::env::temp_dir().join(format!("openclaw-gemini-env-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(".env"),
+            "# comment\nGEMINI_MODEL=x\nexport GOOGLE_CLOUD_PROJECT=\"from-dotenv\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_dotenv_value(&dir.join(".env"), "GOOGLE_CLOUD_PROJECT").as_deref(),
+            Some("from-dotenv")
+        );
+        assert_eq!(
+            read_dotenv_value(&dir.join(".env"), "GOOGLE_CLOUD_PROJECT_ID"),
+            None
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn claude_credentials_without_expiry_assume_a_short_lifetime() {
+        let before = now_millis();
+        let credential = load_claude_fixture(
+            "no-expiry",
+            r#"{ "claudeAiOauth": { "accessToken": "a", "refreshToken": "r" } }"#,
+        )
+        .unwrap();
+        assert!(credential.expires >= before + ASSUMED_TOKEN_LIFETIME_MS);
+        assert!(credential.expires <= now_millis() + ASSUMED_TOKEN_LIFETIME_MS);
+        assert_eq!(
+            credential.credential.get("expires"),
+            Some(&serde_json::json!(credential.expires))
+        );
+    }
+}
@@ -1,5 +1,24 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod auth_monitor;
+mod auth_profiles;
+mod codex_sync;
+mod config_backup;
+mod config_txn;
+mod config_validation;
+mod config_watcher;
+mod gateway_endpoint;
+mod gateway_health;
+mod gateway_log;
+mod gateway_supervisor;
+mod json5_edit;
+mod local_credentials;
+mod model_probe;
+mod openclaw_agents;
+mod openclaw_config;
+mod profiles;
+mod util;
+
 use base64::engine::general_purpose::{URL_SAFE, URL_SAFE_NO_PAD};
 use base64::Engine as _;
 use serde::{Deserialize, Serialize};
@@ -8,16 +27,24 @@ use std::fs;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
-use std::sync::{Mutex, OnceLock};
-use std::time::Duration;
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
 use tauri::{Emitter, Manager};
 
-const OFFICIAL_WEB_URL: &str = "http://127.0.0.1:18789/";
 const BOOTSTRAP_LOG_EVENT: &str = "bootstrap-log";
-const CLAUDE_KEYCHAIN_SERVICE: &str = "Claude Code-credentials";
+const OFFICIAL_WEB_PROGRESS_EVENT: &str = "official-web-progress";
+const OFFICIAL_WEB_WAIT_DEFAULT_MS: u64 = 12_000;
+const OFFICIAL_WEB_WAIT_MAX_MS: u64 = 120_000;
+const OFFICIAL_WEB_POLL_INTERVAL: Duration = Duration::from_millis(400);
 const DEFAULT_OPENCLAW_AGENT_ID: &str = "main";
-const OPENAI_CODEX_DEFAULT_MODEL: &str = "openai-codex/gpt-5.3-codex";
+const CONFIG_BACKUP_KIND_OPENCLAW: &str = "openclaw-config";
+const CONFIG_BACKUP_KIND_AUTH_PROFILES: &str = "auth-profiles";
+
+// Each wait for the official web takes the next id; a cancel names the wait it
+// targets, so it can never stop a later wait.
+static OFFICIAL_WEB_WAIT_ID: AtomicU64 = AtomicU64::new(0);
+static OFFICIAL_WEB_WAIT_CANCELLED_ID: AtomicU64 = AtomicU64::new(0);
 
 const FALLBACK_OAUTH_PROVIDERS: &[&str] = &[
     "openai-codex",
@@ -102,11 +129,26 @@ struct OfficialWebStatus {
     running: bool,
     started: bool,
     url: String,
+    port: u16,
+    // Set when the port is held by a process other than an OpenClaw gateway.
+    suggested_port: Option<u16>,
+    state: gateway_health::GatewayHealthState,
+    health: Option<gateway_health::GatewayHealthProbe>,
     command_hint: String,
     message: String,
     error: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct OfficialWebWaitProgress {
+    wait_id: u64,
+    attempt: u32,
+    elapsed_ms: u64,
+    deadline_ms: u64,
+    state: gateway_health::GatewayHealthState,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct OpenOfficialWebResult {
@@ -129,26 +171,57 @@ struct BootstrapStatus {
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-struct LocalOAuthToolStatus {
-    id: String,
-    label: String,
-    provider_id: String,
-    cli_found: bool,
-    auth_detected: bool,
-    source: String,
-    detail: Option<String>,
+struct AuthProfileDeleteResult {
+    profile_id: String,
+    agent_ids: Vec<String>,
+    // False while another agent still holds the profile; the shared config
+    // entry is kept for it.
+    config_cleaned: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-struct LocalCodexReuseResult {
+struct LocalAuthReuseResult {
     reused: bool,
     profile_id: Option<String>,
+    // Agents whose auth-profiles.json received the credential.
+    agent_ids: Vec<String>,
     model: Option<String>,
     message: String,
     error: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ApiKeySaveResult {
+    ok: bool,
+    profile_id: String,
+    agent_ids: Vec<String>,
+    model: Option<String>,
+    model_switched: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ApiKeyValidationStatus {
+    provider_id: String,
+    endpoint: String,
+    valid: bool,
+    http_status: Option<u16>,
+    models: Vec<String>,
+    error_kind: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ConfigBackupDiff {
+    backup: config_backup::ConfigBackupEntry,
+    current_exists: bool,
+    identical: bool,
+    diff: String,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct BrowserDetectedExecutable {
@@ -188,27 +261,24 @@ struct BrowserRelayDiagnostic {
 }
 
 #[derive(Deserialize)]
-struct LocalCodexAuthFile {
-    tokens: Option<LocalCodexAuthTokens>,
+struct ModelsStatusJson {
+    auth: Option<ModelsStatusAuth>,
 }
 
 #[derive(Deserialize)]
-struct LocalCodexAuthTokens {
-    access_token: Option<String>,
-    refresh_token: Option<String>,
-    account_id: Option<String>,
-    id_token: Option<String>,
+struct ModelsStatusAuth {
+    #[serde(rename = "providersWithOAuth")]
+    providers_with_oauth: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
-struct ModelsStatusJson {
-    auth: Option<ModelsStatusAuth>,
+struct ProviderModelsResponse {
+    data: Option<Vec<ProviderModel>>,
 }
 
 #[derive(Deserialize)]
-struct ModelsStatusAuth {
-    #[serde(rename = "providersWithOAuth")]
-    providers_with_oauth: Option<Vec<String>>,
+struct ProviderModel {
+    id: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -255,7 +325,8 @@ fn read_env_path(name: &str) -> Option<PathBuf> {
     }
 }
 
-fn resolve_openclaw_state_dir() -> PathBuf {
+// State dir and config path of the profile the app was launched with.
+fn resolve_launch_state_dir() -> PathBuf {
     if let Some(state_dir) = read_env_path("OPENCLAW_STATE_DIR") {
         return state_dir;
     }
@@ -265,60 +336,326 @@ fn resolve_openclaw_state_dir() -> PathBuf {
     PathBuf::from(".openclaw")
 }
 
-fn resolve_openclaw_config_path() -> PathBuf {
+fn resolve_launch_config_path() -> PathBuf {
     if let Some(config_path) = read_env_path("OPENCLAW_CONFIG_PATH") {
         return config_path;
     }
-    resolve_openclaw_state_dir().join("openclaw.json")
+    resolve_launch_state_dir().join("openclaw.json")
 }
 
-fn load_openclaw_config_value() -> serde_json::Value {
+fn resolve_openclaw_state_dir() -> PathBuf {
+    match profiles::active() {
+        Some(profile) => profile.state_dir(),
+        None => resolve_launch_state_dir(),
+    }
+}
+
+fn resolve_openclaw_config_path() -> PathBuf {
+    match profiles::active() {
+        Some(profile) => profile.config_path(),
+        None => resolve_launch_config_path(),
+    }
+}
+
+fn resolve_config_backups_dir() -> PathBuf {
+    resolve_openclaw_state_dir().join("backups")
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ConfigParseError {
+    path: String,
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>,
+}
+
+impl ConfigParseError {
+    fn describe(&self) -> String {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => format!(
+                "{} is corrupt (line {}, column {}): {}",
+                self.path, line, column, self.message
+            ),
+            _ => format!("{} is corrupt: {}", self.path, self.message),
+        }
+    }
+
+    fn refuse_write(&self) -> String {
+        format!(
+            "{}. Refusing to overwrite it; fix the file or restore a backup first.",
+            self.describe()
+        )
+    }
+}
+
+enum ConfigLoad {
+    Missing,
+    Parsed(serde_json::Value),
+    Invalid(ConfigParseError),
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct OpenClawConfigStatus {
+    path: String,
+    state: String,
+    error: Option<ConfigParseError>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ConfigValidationReport {
+    path: String,
+    valid: bool,
+    diagnostics: Vec<config_validation::ConfigDiagnostic>,
+}
+
+fn parse_openclaw_config_text(
+    path: &Path,
+    content: &str,
+) -> Result<serde_json::Value, ConfigParseError> {
+    let parse_error = |message: String, location: Option<(usize, usize)>| ConfigParseError {
+        path: path.to_string_lossy().to_string(),
+        message,
+        line: location.map(|(line, _)| line),
+        column: location.map(|(_, column)| column),
+    };
+
+    if content.trim().is_empty() {
+        return Ok(serde_json::json!({}));
+    }
+    let value = match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(value) => value,
+        Err(_) => json5::from_str::<serde_json::Value>(content).map_err(|err| {
+            let json5::Error::Message { msg, location } = err;
+            parse_error(msg, location.map(|loc| (loc.line, loc.column)))
+        })?,
+    };
+    if !value.is_object() {
+        return Err(parse_error("top-level value must be an object".to_string(), None));
+    }
+    Ok(value)
+}
+
+fn load_openclaw_config() -> ConfigLoad {
     let config_path = resolve_openclaw_config_path();
     if !config_path.exists() {
-        return serde_json::json!({});
+        return ConfigLoad::Missing;
     }
 
-    let content = fs::read_to_string(&config_path).unwrap_or_default();
-    serde_json::from_str::<serde_json::Value>(&content)
-        .or_else(|_| json5::from_str::<serde_json::Value>(&content))
-        .unwrap_or_else(|_| serde_json::json!({}))
+    match fs::read_to_string(&config_path) {
+        Ok(content) => match parse_openclaw_config_text(&config_path, &content) {
+            Ok(value) => ConfigLoad::Parsed(value),
+            Err(error) => ConfigLoad::Invalid(error),
+        },
+        Err(err) => ConfigLoad::Invalid(ConfigParseError {
+            path: config_path.to_string_lossy().to_string(),
+            message: err.to_string(),
+            line: None,
+            column: None,
+        }),
+    }
 }
 
-fn save_openclaw_config_value(value: &serde_json::Value) -> Result<(), String> {
-    let config_path = resolve_openclaw_config_path();
-    if let Some(parent) = config_path.parent() {
-        fs::create_dir_all(parent).map_err(|err| {
-            format!(
-                "Failed to create config dir {}: {}",
-                parent.to_string_lossy(),
-                err
-            )
-        })?;
+// Lossy view for read-only status checks. Writers go through
+// update_openclaw_config, which refuses to touch a corrupt file.
+fn load_openclaw_config_value() -> serde_json::Value {
+    match load_openclaw_config() {
+        ConfigLoad::Parsed(value) => value,
+        ConfigLoad::Missing | ConfigLoad::Invalid(_) => serde_json::json!({}),
     }
+}
 
-    fs::write(
-        &config_path,
-        serde_json::to_string_pretty(value)
-            .map_err(|err| format!("Failed to serialize OpenClaw config: {}", err))?,
-    )
-    .map_err(|err| format!("Failed to write {}: {}", config_path.to_string_lossy(), err))
+#[tauri::command]
+fn get_openclaw_config_status() -> OpenClawConfigStatus {
+    let path = resolve_openclaw_config_path().to_string_lossy().to_string();
+    match load_openclaw_config() {
+        ConfigLoad::Missing => OpenClawConfigStatus {
+            path,
+            state: "missing".to_string(),
+            error: None,
+        },
+        ConfigLoad::Parsed(_) => OpenClawConfigStatus {
+            path,
+            state: "ok".to_string(),
+            error: None,
+        },
+        ConfigLoad::Invalid(error) => OpenClawConfigStatus {
+            path,
+            state: "corrupt".to_string(),
+            error: Some(error),
+        },
+    }
 }
 
-fn resolve_openclaw_agent_dir() -> PathBuf {
-    if let Some(agent_dir) = read_env_path("OPENCLAW_AGENT_DIR") {
-        return agent_dir;
+#[tauri::command]
+fn validate_openclaw_config() -> ConfigValidationReport {
+    let path = resolve_openclaw_config_path().to_string_lossy().to_string();
+    let diagnostics = match load_openclaw_config() {
+        ConfigLoad::Missing => vec![config_validation::ConfigDiagnostic {
+            severity: config_validation::Severity::Info,
+            pointer: String::new(),
+            message: "Config file does not exist yet; OpenClaw will use its defaults.".to_string(),
+            hint: None,
+        }],
+        ConfigLoad::Invalid(error) => vec![config_validation::ConfigDiagnostic {
+            severity: config_validation::Severity::Error,
+            pointer: String::new(),
+            message: error.describe(),
+            hint: Some("Fix the syntax error or restore a config backup.".to_string()),
+        }],
+        ConfigLoad::Parsed(value) => {
+            config_validation::validate(&value, read_auth_profile_ids().as_ref())
+        }
+    };
+
+    ConfigValidationReport {
+        path,
+        valid: !diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == config_validation::Severity::Error),
+        diagnostics,
+    }
+}
+
+fn update_openclaw_config<T>(
+    apply: impl FnOnce(&mut openclaw_config::OpenClawConfig) -> Result<T, String>,
+) -> Result<T, String> {
+    let config_path = resolve_openclaw_config_path();
+    let txn = config_txn::ConfigTransaction::begin(&config_path)?.backup_to(
+        &resolve_config_backups_dir(),
+        CONFIG_BACKUP_KIND_OPENCLAW,
+        "desktop write",
+    );
+    let original_text = txn.read()?;
+    let original = match original_text.as_deref() {
+        Some(text) => parse_openclaw_config_text(&config_path, text)
+            .map_err(|error| error.refuse_write())?,
+        None => serde_json::json!({}),
+    };
+    let mut config = openclaw_config::OpenClawConfig::from_value(original.clone())?;
+    let baseline = config.to_value()?;
+
+    let result = apply(&mut config)?;
+    let config_value = config.to_value()?;
+    if config_value != baseline {
+        // Patch the user's JSON5 text in place so comments and layout survive.
+        // Only plain JSON, which has no comments to lose, may be rewritten
+        // whole when that fails.
+        let rewrite = || {
+            serde_json::to_string_pretty(&config_value)
+                .map_err(|err| format!("Failed to serialize OpenClaw config: {}", err))
+        };
+        let serialized = match original_text.as_deref() {
+            None => rewrite()?,
+            Some(text) => match json5_edit::apply_value_diff(text, &original, &config_value) {
+                Ok(edited) => edited,
+                Err(error) if serde_json::from_str::<serde_json::Value>(text).is_ok() => {
+                    gateway_log::note(&format!(
+                        "Rewrote {} in full after an in-place edit failed: {}",
+                        config_path.to_string_lossy(),
+                        error
+                    ));
+                    rewrite()?
+                }
+                Err(error) => {
+                    return Err(format!(
+                        "Failed to update {} without losing its comments: {}",
+                        config_path.to_string_lossy(),
+                        error
+                    ))
+                }
+            },
+        };
+        txn.commit(&serialized)?;
     }
-    if let Some(agent_dir) = read_env_path("PI_CODING_AGENT_DIR") {
-        return agent_dir;
+    Ok(result)
+}
+
+fn resolve_openclaw_agent_dir() -> PathBuf {
+    // Agent dir overrides from the environment belong to the launch profile.
+    if profiles::active().is_none() {
+        if let Some(agent_dir) = read_env_path("OPENCLAW_AGENT_DIR") {
+            return agent_dir;
+        }
+        if let Some(agent_dir) = read_env_path("PI_CODING_AGENT_DIR") {
+            return agent_dir;
+        }
     }
-    resolve_openclaw_state_dir()
-        .join("agents")
-        .join(DEFAULT_OPENCLAW_AGENT_ID)
-        .join("agent")
+    openclaw_agents::agent_dir(&resolve_openclaw_state_dir(), DEFAULT_OPENCLAW_AGENT_ID)
 }
 
 fn resolve_openclaw_auth_profiles_path() -> PathBuf {
-    resolve_openclaw_agent_dir().join("auth-profiles.json")
+    resolve_openclaw_agent_dir().join(openclaw_agents::AUTH_PROFILES_FILE)
+}
+
+// The default agent first (honoring env overrides), then every other agent
+// found under the state dir.
+fn list_openclaw_agent_dirs() -> Vec<(String, PathBuf)> {
+    let state_dir = resolve_openclaw_state_dir();
+    let mut agents = vec![(
+        DEFAULT_OPENCLAW_AGENT_ID.to_string(),
+        resolve_openclaw_agent_dir(),
+    )];
+    for id in openclaw_agents::discover(&state_dir) {
+        if id != DEFAULT_OPENCLAW_AGENT_ID {
+            let dir = openclaw_agents::agent_dir(&state_dir, &id);
+            agents.push((id, dir));
+        }
+    }
+    agents
+}
+
+// Auth-profiles files to write for an optional agent id: none means the
+// default agent, `*` means every agent.
+fn resolve_agent_auth_targets(agent_id: Option<&str>) -> Result<Vec<(String, PathBuf)>, String> {
+    let agent_id = agent_id
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .unwrap_or(DEFAULT_OPENCLAW_AGENT_ID);
+    let agents = list_openclaw_agent_dirs();
+    let targets: Vec<(String, PathBuf)> = if agent_id == openclaw_agents::ALL_AGENTS {
+        agents
+    } else {
+        if !openclaw_agents::is_valid_agent_id(agent_id) {
+            return Err(format!("Invalid agent id '{}'.", agent_id));
+        }
+        let found = agents.into_iter().find(|(id, _)| id == agent_id);
+        vec![found.ok_or_else(|| format!("Agent '{}' does not exist.", agent_id))?]
+    };
+    Ok(targets
+        .into_iter()
+        .map(|(id, dir)| (id, dir.join(openclaw_agents::AUTH_PROFILES_FILE)))
+        .collect())
+}
+
+fn update_auth_profiles_at<T>(
+    auth_profiles_path: &Path,
+    apply: impl FnOnce(&mut serde_json::Value) -> Result<T, String>,
+) -> Result<T, String> {
+    let txn = config_txn::ConfigTransaction::begin(auth_profiles_path)?
+        .private()
+        .backup_to(
+            &resolve_config_backups_dir(),
+            CONFIG_BACKUP_KIND_AUTH_PROFILES,
+            "desktop write",
+        );
+    let original = match txn.read()? {
+        Some(text) => parse_openclaw_config_text(auth_profiles_path, &text)
+            .map_err(|error| error.refuse_write())?,
+        None => serde_json::json!({}),
+    };
+    let mut auth_profiles_value = original.clone();
+
+    let result = apply(&mut auth_profiles_value)?;
+    if auth_profiles_value != original {
+        txn.commit(
+            &serde_json::to_string_pretty(&auth_profiles_value)
+                .map_err(|err| format!("Failed to serialize auth-profiles: {}", err))?,
+        )?;
+    }
+    Ok(result)
 }
 
 fn decode_jwt_payload(token: &str) -> Option<serde_json::Value> {
@@ -378,249 +715,112 @@ fn jwt_openai_account_id(token: &str) -> Option<String> {
     }
 }
 
-fn sync_local_codex_auth_to_openclaw(set_default_model: bool) -> Result<LocalCodexReuseResult, String> {
-    let codex_auth_path = resolve_codex_auth_path();
-    let raw = fs::read_to_string(&codex_auth_path)
-        .map_err(|err| format!("Failed to read {}: {}", codex_auth_path.to_string_lossy(), err))?;
-    let parsed = serde_json::from_str::<LocalCodexAuthFile>(&raw)
-        .map_err(|err| format!("Invalid Codex auth file format: {}", err))?;
-    let tokens = parsed
-        .tokens
-        .ok_or_else(|| "Codex auth tokens field is missing.".to_string())?;
-
-    let access_token = tokens.access_token.unwrap_or_default().trim().to_string();
-    let refresh_token = tokens.refresh_token.unwrap_or_default().trim().to_string();
-    if access_token.is_empty() || refresh_token.is_empty() {
-        return Err("Codex auth file is missing access_token or refresh_token.".to_string());
-    }
-
-    let account_id = tokens
-        .account_id
-        .as_deref()
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .map(str::to_string)
-        .or_else(|| jwt_openai_account_id(&access_token));
-    let expires = jwt_exp_millis(&access_token)
-        .or_else(|| tokens.id_token.as_deref().and_then(jwt_exp_millis))
-        .unwrap_or_else(|| {
-            let now_ms = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .map(|d| d.as_millis() as i64)
-                .unwrap_or(0);
-            now_ms + 60 * 60 * 1000
-        });
-    let email = jwt_email(&access_token).or_else(|| tokens.id_token.as_deref().and_then(jwt_email));
-    let profile_id = email
-        .as_ref()
-        .map(|mail| format!("openai-codex:{}", mail))
-        .unwrap_or_else(|| "openai-codex:default".to_string());
-
-    let auth_profiles_path = resolve_openclaw_auth_profiles_path();
-    if let Some(parent) = auth_profiles_path.parent() {
-        fs::create_dir_all(parent).map_err(|err| {
-            format!(
-                "Failed to create auth profile dir {}: {}",
-                parent.to_string_lossy(),
-                err
-            )
-        })?;
-    }
+fn upsert_auth_profile_credential(
+    auth_profiles_path: &Path,
+    profile_id: &str,
+    credential: serde_json::Map<String, serde_json::Value>,
+) -> Result<(), String> {
+    update_auth_profiles_at(auth_profiles_path, |auth_profiles_value| {
+        let auth_profiles_obj = auth_profiles_value
+            .as_object_mut()
+            .ok_or_else(|| "Failed to parse auth-profiles root object.".to_string())?;
+        auth_profiles_obj.insert("version".to_string(), serde_json::json!(1));
+        let profiles_entry = auth_profiles_obj
+            .entry("profiles".to_string())
+            .or_insert_with(|| serde_json::json!({}));
+        if !profiles_entry.is_object() {
+            *profiles_entry = serde_json::json!({});
+        }
+        let profiles_obj = profiles_entry
+            .as_object_mut()
+            .ok_or_else(|| "Failed to parse auth-profiles profiles object.".to_string())?;
+        profiles_obj.insert(profile_id.to_string(), serde_json::Value::Object(credential));
+        Ok(())
+    })
+}
 
-    let mut auth_profiles_value = if auth_profiles_path.exists() {
-        let content = fs::read_to_string(&auth_profiles_path).unwrap_or_default();
-        serde_json::from_str::<serde_json::Value>(&content).unwrap_or_else(|_| serde_json::json!({}))
+fn sync_local_auth_to_openclaw(
+    source: &dyn local_credentials::LocalCredentialSource,
+    set_default_model: bool,
+    agent_id: Option<&str>,
+) -> Result<LocalAuthReuseResult, String> {
+    let targets = resolve_agent_auth_targets(agent_id)?;
+    let local = source.load()?;
+    let default_model = if set_default_model {
+        source.default_model()
     } else {
-        serde_json::json!({})
+        None
     };
+    apply_local_oauth_credential(local, targets, default_model)
+}
 
-    if !auth_profiles_value.is_object() {
-        auth_profiles_value = serde_json::json!({});
-    }
-    let auth_profiles_obj = auth_profiles_value
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse auth-profiles root object.".to_string())?;
-    auth_profiles_obj.insert("version".to_string(), serde_json::json!(1));
-    let profiles_entry = auth_profiles_obj
-        .entry("profiles".to_string())
-        .or_insert_with(|| serde_json::json!({}));
-    if !profiles_entry.is_object() {
-        *profiles_entry = serde_json::json!({});
-    }
-    let profiles_obj = profiles_entry
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse auth-profiles profiles object.".to_string())?;
-
-    let mut credential = serde_json::Map::new();
-    credential.insert("type".to_string(), serde_json::json!("oauth"));
-    credential.insert("provider".to_string(), serde_json::json!("openai-codex"));
-    credential.insert("access".to_string(), serde_json::json!(access_token));
-    credential.insert("refresh".to_string(), serde_json::json!(refresh_token));
-    credential.insert("expires".to_string(), serde_json::json!(expires));
-    if let Some(value) = &account_id {
-        credential.insert("accountId".to_string(), serde_json::json!(value));
-    }
-    if let Some(value) = &email {
-        credential.insert("email".to_string(), serde_json::json!(value));
-    }
-    profiles_obj.insert(profile_id.clone(), serde_json::Value::Object(credential));
-
-    fs::write(
-        &auth_profiles_path,
-        serde_json::to_string_pretty(&auth_profiles_value)
-            .map_err(|err| format!("Failed to serialize auth-profiles: {}", err))?,
-    )
-    .map_err(|err| {
-        format!(
-            "Failed to write {}: {}",
-            auth_profiles_path.to_string_lossy(),
-            err
-        )
-    })?;
-
-    let config_path = resolve_openclaw_config_path();
-    if let Some(parent) = config_path.parent() {
-        fs::create_dir_all(parent).map_err(|err| {
-            format!(
-                "Failed to create config dir {}: {}",
-                parent.to_string_lossy(),
-                err
-            )
-        })?;
+fn reuse_local_auth_with(
+    source: &dyn local_credentials::LocalCredentialSource,
+    set_default_model: Option<bool>,
+    agent_id: Option<&str>,
+) -> LocalAuthReuseResult {
+    match sync_local_auth_to_openclaw(source, set_default_model.unwrap_or(true), agent_id) {
+        Ok(result) => result,
+        Err(error) => LocalAuthReuseResult {
+            reused: false,
+            profile_id: None,
+            agent_ids: Vec::new(),
+            model: None,
+            message: format!("Failed to reuse local {} auth.", source.label()),
+            error: Some(error),
+        },
     }
+}
 
-    let mut config_value = if config_path.exists() {
-        let content = fs::read_to_string(&config_path).unwrap_or_default();
-        serde_json::from_str::<serde_json::Value>(&content).unwrap_or_else(|_| serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-    if !config_value.is_object() {
-        config_value = serde_json::json!({});
-    }
-
-    let config_obj = config_value
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse config root object.".to_string())?;
-    let auth_entry = config_obj
-        .entry("auth".to_string())
-        .or_insert_with(|| serde_json::json!({}));
-    if !auth_entry.is_object() {
-        *auth_entry = serde_json::json!({});
-    }
-    let auth_obj = auth_entry
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse config auth object.".to_string())?;
-
-    let cfg_profiles = auth_obj
-        .entry("profiles".to_string())
-        .or_insert_with(|| serde_json::json!({}));
-    if !cfg_profiles.is_object() {
-        *cfg_profiles = serde_json::json!({});
-    }
-    let cfg_profiles_obj = cfg_profiles
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse config auth.profiles object.".to_string())?;
-    let mut profile_meta = serde_json::Map::new();
-    profile_meta.insert("provider".to_string(), serde_json::json!("openai-codex"));
-    profile_meta.insert("mode".to_string(), serde_json::json!("oauth"));
-    if let Some(value) = &email {
-        profile_meta.insert("email".to_string(), serde_json::json!(value));
-    }
-    cfg_profiles_obj.insert(profile_id.clone(), serde_json::Value::Object(profile_meta));
-
-    let order_entry = auth_obj
-        .entry("order".to_string())
-        .or_insert_with(|| serde_json::json!({}));
-    if !order_entry.is_object() {
-        *order_entry = serde_json::json!({});
-    }
-    let order_obj = order_entry
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse config auth.order object.".to_string())?;
-    let mut next_order = vec![profile_id.clone()];
-    if let Some(existing) = order_obj.get("openai-codex").and_then(|v| v.as_array()) {
-        for item in existing {
-            if let Some(id) = item.as_str() {
-                let trimmed = id.trim();
-                if !trimmed.is_empty() && !next_order.iter().any(|current| current == trimmed) {
-                    next_order.push(trimmed.to_string());
-                }
-            }
-        }
+// Writes the credential into every target agent and registers it in the
+// config. `default_model` is the model to make primary, and the prefixes of
+// primary models it is allowed to replace.
+fn apply_local_oauth_credential(
+    local: local_credentials::LocalOAuthCredential,
+    targets: Vec<(String, PathBuf)>,
+    default_model: Option<local_credentials::DefaultModel>,
+) -> Result<LocalAuthReuseResult, String> {
+    let local_credentials::LocalOAuthCredential {
+        label,
+        provider_id,
+        profile_id,
+        email,
+        credential,
+        ..
+    } = local;
+    for (_, auth_profiles_path) in &targets {
+        upsert_auth_profile_credential(auth_profiles_path, &profile_id, credential.clone())?;
     }
-    order_obj.insert("openai-codex".to_string(), serde_json::json!(next_order));
 
-    let mut selected_model: Option<String> = None;
-    if set_default_model {
-        let agents_entry = config_obj
-            .entry("agents".to_string())
-            .or_insert_with(|| serde_json::json!({}));
-        if !agents_entry.is_object() {
-            *agents_entry = serde_json::json!({});
-        }
-        let agents_obj = agents_entry
-            .as_object_mut()
-            .ok_or_else(|| "Failed to parse config agents object.".to_string())?;
-        let defaults_entry = agents_obj
-            .entry("defaults".to_string())
-            .or_insert_with(|| serde_json::json!({}));
-        if !defaults_entry.is_object() {
-            *defaults_entry = serde_json::json!({});
-        }
-        let defaults_obj = defaults_entry
-            .as_object_mut()
-            .ok_or_else(|| "Failed to parse config agents.defaults object.".to_string())?;
+    let selected_model = update_openclaw_config(|config| {
+        config.upsert_auth_profile(&profile_id, provider_id, "oauth", email.as_deref());
+        config.prepend_auth_order(provider_id, &profile_id);
 
-        let current_primary = match defaults_obj.get("model") {
-            Some(serde_json::Value::String(model)) => model.trim().to_string(),
-            Some(serde_json::Value::Object(model_obj)) => model_obj
-                .get("primary")
-                .and_then(|v| v.as_str())
-                .unwrap_or_default()
-                .trim()
-                .to_string(),
-            _ => String::new(),
+        let Some((model, replaceable)) = default_model else {
+            return Ok(None);
         };
+        let current_primary = config.primary_model().unwrap_or_default().to_string();
         let should_override = current_primary.is_empty()
-            || current_primary.starts_with("anthropic/")
-            || current_primary.starts_with("openai/");
+            || replaceable
+                .iter()
+                .any(|prefix| current_primary.starts_with(prefix));
 
         if should_override {
-            let model_entry = defaults_obj
-                .entry("model".to_string())
-                .or_insert_with(|| serde_json::json!({}));
-            match model_entry {
-                serde_json::Value::Object(model_obj) => {
-                    model_obj.insert(
-                        "primary".to_string(),
-                        serde_json::json!(OPENAI_CODEX_DEFAULT_MODEL),
-                    );
-                }
-                _ => {
-                    *model_entry = serde_json::json!({
-                        "primary": OPENAI_CODEX_DEFAULT_MODEL
-                    });
-                }
-            }
-            selected_model = Some(OPENAI_CODEX_DEFAULT_MODEL.to_string());
+            config.set_primary_model(model);
+            Ok(Some(model.to_string()))
         } else if !current_primary.is_empty() {
-            selected_model = Some(current_primary);
+            Ok(Some(current_primary))
+        } else {
+            Ok(None)
         }
-    }
-
-    fs::write(
-        &config_path,
-        serde_json::to_string_pretty(&config_value)
-            .map_err(|err| format!("Failed to serialize config: {}", err))?,
-    )
-    .map_err(|err| format!("Failed to write {}: {}", config_path.to_string_lossy(), err))?;
+    })?;
 
-    Ok(LocalCodexReuseResult {
+    Ok(LocalAuthReuseResult {
         reused: true,
         profile_id: Some(profile_id),
+        agent_ids: targets.into_iter().map(|(id, _)| id).collect(),
         model: selected_model,
-        message: "Local Codex auth has been synced into OpenClaw.".to_string(),
+        message: format!("Local {} auth has been synced into OpenClaw.", label),
         error: None,
     })
 }
@@ -664,18 +864,52 @@ fn percent_encode_component(value: &str) -> String {
     encoded
 }
 
-fn resolve_official_dashboard_url() -> String {
-    if let Some(token) = read_gateway_auth_token() {
-        return format!("{}#token={}", OFFICIAL_WEB_URL, percent_encode_component(&token));
+fn read_config_gateway_port(config_path: &Path) -> Option<u16> {
+    let content = fs::read_to_string(config_path).ok()?;
+    parse_openclaw_config_text(config_path, &content)
+        .ok()?
+        .pointer("/gateway/port")
+        .and_then(|v| v.as_i64())
+        .filter(|port| config_validation::is_valid_tcp_port(*port))
+        .map(|port| port as u16)
+}
+
+fn read_config_gateway_bind(config_path: &Path) -> gateway_endpoint::GatewayBind {
+    let Some(gateway) = fs::read_to_string(config_path)
+        .ok()
+        .and_then(|content| parse_openclaw_config_text(config_path, &content).ok())
+        .and_then(|config| config.get("gateway").cloned())
+    else {
+        return gateway_endpoint::GatewayBind::default();
+    };
+    let text = |key: &str| {
+        gateway
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+    };
+    gateway_endpoint::GatewayBind {
+        mode: text("bind"),
+        custom_host: text("customBindHost"),
     }
-    OFFICIAL_WEB_URL.to_string()
 }
 
-fn resolve_claude_credentials_path() -> PathBuf {
-    if let Some(home) = resolve_user_home() {
-        return home.join(".claude").join(".credentials.json");
+fn resolve_gateway_endpoint() -> gateway_endpoint::GatewayEndpoint {
+    let bind = read_config_gateway_bind(&resolve_openclaw_config_path());
+    if let Some(profile) = profiles::active() {
+        return gateway_endpoint::for_profile(profile.port, bind);
     }
-    PathBuf::from(".claude/.credentials.json")
+    gateway_endpoint::resolve(read_config_gateway_port(&resolve_launch_config_path()), bind)
+}
+
+fn resolve_official_dashboard_url(endpoint: &gateway_endpoint::GatewayEndpoint) -> String {
+    let base_url = endpoint.base_url();
+    if let Some(token) = read_gateway_auth_token() {
+        return format!("{}#token={}", base_url, percent_encode_component(&token));
+    }
+    base_url
 }
 
 fn command_exists(binary: &str, args: &[&str]) -> bool {
@@ -887,56 +1121,6 @@ fn ensure_browser_defaults(
     app: &tauri::AppHandle,
     logs: &mut Vec<String>,
 ) -> Result<(), String> {
-    let config_path = resolve_openclaw_config_path();
-    if let Some(parent) = config_path.parent() {
-        fs::create_dir_all(parent).map_err(|err| {
-            format!(
-                "Failed to create config dir {}: {}",
-                parent.to_string_lossy(),
-                err
-            )
-        })?;
-    }
-
-    let mut config_value = if config_path.exists() {
-        let content = fs::read_to_string(&config_path).unwrap_or_default();
-        serde_json::from_str::<serde_json::Value>(&content)
-            .or_else(|_| json5::from_str::<serde_json::Value>(&content))
-            .unwrap_or_else(|_| serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-    if !config_value.is_object() {
-        config_value = serde_json::json!({});
-    }
-
-    let config_obj = config_value
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse OpenClaw config root object.".to_string())?;
-    let browser_entry = config_obj
-        .entry("browser".to_string())
-        .or_insert_with(|| serde_json::json!({}));
-    if !browser_entry.is_object() {
-        *browser_entry = serde_json::json!({});
-    }
-    let browser_obj = browser_entry
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse OpenClaw config browser object.".to_string())?;
-
-    let current_executable = browser_obj
-        .get("executablePath")
-        .and_then(|value| value.as_str())
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(str::to_string);
-    let current_profile = browser_obj
-        .get("defaultProfile")
-        .and_then(|value| value.as_str())
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(str::to_string);
-
-    let mut changed = false;
     let candidates = detect_local_browser_candidates();
     if candidates.is_empty() {
         push_bootstrap_log(
@@ -958,63 +1142,59 @@ fn ensure_browser_defaults(
         );
     }
 
-    if browser_obj
-        .get("enabled")
-        .and_then(|value| value.as_bool())
-        .is_none()
-    {
-        browser_obj.insert("enabled".to_string(), serde_json::json!(true));
-        changed = true;
-    }
+    let changed = update_openclaw_config(|config| {
+        let current_executable = config.browser_executable_path().map(str::to_string);
+        let current_profile = config.browser_default_profile().map(str::to_string);
+        let browser = config.browser_mut();
 
-    if current_profile.is_none() {
-        browser_obj.insert("defaultProfile".to_string(), serde_json::json!("openclaw"));
-        push_bootstrap_log(
-            app,
-            logs,
-            "Browser config: set browser.defaultProfile=openclaw",
-        );
-        changed = true;
-    }
+        let mut changed = false;
+        if browser.enabled.is_none() {
+            browser.enabled = Some(true);
+            changed = true;
+        }
 
-    if current_executable.is_none() {
-        if let Some(chosen) = candidates.first() {
-            browser_obj.insert(
-                "executablePath".to_string(),
-                serde_json::json!(chosen.path.to_string_lossy().to_string()),
-            );
+        if current_profile.is_none() {
+            browser.default_profile = Some("openclaw".to_string());
             push_bootstrap_log(
                 app,
                 logs,
-                format!(
-                    "Browser config: set browser.executablePath={} ({})",
-                    chosen.path.to_string_lossy(),
-                    chosen.kind
-                ),
+                "Browser config: set browser.defaultProfile=openclaw",
             );
             changed = true;
-        } else {
-            push_bootstrap_log(
+        }
+
+        if current_executable.is_none() {
+            if let Some(chosen) = candidates.first() {
+                browser.executable_path = Some(chosen.path.to_string_lossy().to_string());
+                push_bootstrap_log(
+                    app,
+                    logs,
+                    format!(
+                        "Browser config: set browser.executablePath={} ({})",
+                        chosen.path.to_string_lossy(),
+                        chosen.kind
+                    ),
+                );
+                changed = true;
+            } else {
+                push_bootstrap_log(
+                    app,
+                    logs,
+                    "Browser config: keep browser.executablePath unset (auto detection in OpenClaw runtime).",
+                );
+            }
+        } else if let Some(path) = current_executable {
+            push_bootstrap_log(
                 app,
                 logs,
-                "Browser config: keep browser.executablePath unset (auto detection in OpenClaw runtime).",
+                format!("Browser config: existing browser.executablePath={}", path),
             );
         }
-    } else if let Some(path) = current_executable {
-        push_bootstrap_log(
-            app,
-            logs,
-            format!("Browser config: existing browser.executablePath={}", path),
-        );
-    }
+
+        Ok(changed)
+    })?;
 
     if changed {
-        fs::write(
-            &config_path,
-            serde_json::to_string_pretty(&config_value)
-                .map_err(|err| format!("Failed to serialize OpenClaw config: {}", err))?,
-        )
-        .map_err(|err| format!("Failed to write {}: {}", config_path.to_string_lossy(), err))?;
         push_bootstrap_log(app, logs, "Browser config defaults ensured.");
     } else {
         push_bootstrap_log(app, logs, "Browser config already initialized; no changes.");
@@ -1023,12 +1203,9 @@ fn ensure_browser_defaults(
     Ok(())
 }
 
-fn browser_mode_status_from_config(config_value: &serde_json::Value) -> BrowserModeStatus {
-    let default_profile = config_value
-        .pointer("/browser/defaultProfile")
-        .and_then(|value| value.as_str())
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
+fn browser_mode_status_from_config(config: &openclaw_config::OpenClawConfig) -> BrowserModeStatus {
+    let default_profile = config
+        .browser_default_profile()
         .unwrap_or("openclaw")
         .to_string();
 
@@ -1038,12 +1215,7 @@ fn browser_mode_status_from_config(config_value: &serde_json::Value) -> BrowserM
         "openclaw".to_string()
     };
 
-    let executable_path = config_value
-        .pointer("/browser/executablePath")
-        .and_then(|value| value.as_str())
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(str::to_string);
+    let executable_path = config.browser_executable_path().map(str::to_string);
 
     let detected_browsers = detect_local_browser_candidates()
         .into_iter()
@@ -1063,8 +1235,9 @@ fn browser_mode_status_from_config(config_value: &serde_json::Value) -> BrowserM
 
 #[tauri::command]
 fn get_browser_mode_status() -> Result<BrowserModeStatus, String> {
-    let config_value = load_openclaw_config_value();
-    Ok(browser_mode_status_from_config(&config_value))
+    let config = openclaw_config::OpenClawConfig::from_value(load_openclaw_config_value())
+        .unwrap_or_default();
+    Ok(browser_mode_status_from_config(&config))
 }
 
 #[tauri::command]
@@ -1076,38 +1249,15 @@ fn set_browser_mode(mode: String) -> Result<BrowserModeStatus, String> {
         _ => return Err("Unsupported browser mode. Use 'openclaw' or 'chrome'.".to_string()),
     };
 
-    let mut config_value = load_openclaw_config_value();
-    if !config_value.is_object() {
-        config_value = serde_json::json!({});
-    }
-
-    let config_obj = config_value
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse OpenClaw config root object.".to_string())?;
-    let browser_entry = config_obj
-        .entry("browser".to_string())
-        .or_insert_with(|| serde_json::json!({}));
-    if !browser_entry.is_object() {
-        *browser_entry = serde_json::json!({});
-    }
-
-    let browser_obj = browser_entry
-        .as_object_mut()
-        .ok_or_else(|| "Failed to parse OpenClaw config browser object.".to_string())?;
-    browser_obj.insert(
-        "defaultProfile".to_string(),
-        serde_json::json!(target_profile),
-    );
-    if browser_obj
-        .get("enabled")
-        .and_then(|value| value.as_bool())
-        .is_none()
-    {
-        browser_obj.insert("enabled".to_string(), serde_json::json!(true));
-    }
-
-    save_openclaw_config_value(&config_value)?;
-    Ok(browser_mode_status_from_config(&config_value))
+    let config = update_openclaw_config(|config| {
+        let browser = config.browser_mut();
+        browser.default_profile = Some(target_profile.to_string());
+        if browser.enabled.is_none() {
+            browser.enabled = Some(true);
+        }
+        Ok(config.clone())
+    })?;
+    Ok(browser_mode_status_from_config(&config))
 }
 
 fn extract_browser_relay_path(output: &str) -> Option<String> {
@@ -1308,7 +1458,7 @@ fn resolve_browser_relay_url_from_config(config_value: &serde_json::Value) -> St
     let from_port = config_value
         .pointer("/browser/profiles/chrome/cdpPort")
         .and_then(|value| value.as_i64())
-        .filter(|port| *port > 0 && *port <= 65535)
+        .filter(|port| config_validation::is_valid_tcp_port(*port))
         .map(|port| format!("http://127.0.0.1:{}", port));
 
     from_profile
@@ -1539,6 +1689,57 @@ fn resolve_provider_default_model(provider_id: &str) -> Option<&'static str> {
     }
 }
 
+fn normalize_api_key_provider_id(raw: &str) -> Option<String> {
+    let lowered = raw.trim().to_ascii_lowercase();
+    if lowered.is_empty() {
+        return None;
+    }
+    let canonical = match lowered.as_str() {
+        "claude" => "anthropic",
+        "gemini" | "google-gemini" => "google",
+        _ => lowered.as_str(),
+    };
+    Some(canonical.to_string())
+}
+
+fn resolve_provider_api_key_default_model(provider_id: &str) -> Option<&'static str> {
+    match provider_id {
+        "openai" => Some("openai/gpt-5"),
+        "anthropic" => Some("anthropic/claude-sonnet-4-5"),
+        "deepseek" => Some("deepseek/deepseek-chat"),
+        "google" => Some("google/gemini-2.5-pro"),
+        "qwen" => Some("qwen/qwen-max"),
+        _ => None,
+    }
+}
+
+fn resolve_provider_api_base_url(provider_id: &str) -> Option<String> {
+    let base_url = match provider_id {
+        "openai" => "https://api.openai.com/v1",
+        "anthropic" => "https://api.anthropic.com/v1",
+        "deepseek" => "https://api.deepseek.com/v1",
+        "qwen" | "dashscope" => "https://dashscope.aliyuncs.com/compatible-mode/v1",
+        _ => return None,
+    };
+    Some(base_url.to_string())
+}
+
+fn classify_api_key_http_error(status: u16, body: &str) -> &'static str {
+    let lower = body.to_ascii_lowercase();
+    if status == 429
+        || status == 402
+        || lower.contains("insufficient_quota")
+        || lower.contains("insufficient balance")
+        || lower.contains("credit balance")
+    {
+        "quota"
+    } else if status == 401 || status == 403 {
+        "invalid_key"
+    } else {
+        "unexpected"
+    }
+}
+
 fn resolve_openclaw_binary() -> Option<String> {
     let mut candidates = Vec::new();
 
@@ -1713,7 +1914,7 @@ fn push_bootstrap_log(app: &tauri::AppHandle, logs: &mut Vec<String>, message: i
 }
 
 fn run_command(binary: &str, args: &[&str]) -> Result<(bool, String), String> {
-    let output = Command::new(binary)
+    let output = profiles::apply_env(&mut Command::new(binary))
         .args(args)
         .output()
         .map_err(|err| err.to_string())?;
@@ -1727,7 +1928,7 @@ fn run_oauth_login_with_tty(binary: &str, provider_id: &str) -> Result<(bool, St
 
     #[cfg(not(target_os = "windows"))]
     {
-        let output = Command::new("script")
+        let output = profiles::apply_env(&mut Command::new("script"))
             .arg("-q")
             .arg("/dev/null")
             .arg(binary)
@@ -1743,24 +1944,35 @@ fn run_oauth_login_with_tty(binary: &str, provider_id: &str) -> Result<(bool, St
     run_command(binary, &args)
 }
 
+fn read_auth_profile_ids() -> Option<BTreeSet<String>> {
+    let raw = fs::read_to_string(resolve_openclaw_auth_profiles_path()).ok()?;
+    let parsed = serde_json::from_str::<serde_json::Value>(&raw).ok()?;
+    Some(parsed.get("profiles")?.as_object()?.keys().cloned().collect())
+}
+
+// The CLI logs in for whichever agent it resolves to, so any agent's store counts.
 fn provider_has_auth_profile(provider_id: &str) -> bool {
-    let auth_path = resolve_openclaw_auth_profiles_path();
-    let Ok(raw) = fs::read_to_string(auth_path) else {
-        return false;
-    };
-    let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&raw) else {
-        return false;
-    };
-    let Some(profiles) = parsed.get("profiles").and_then(|v| v.as_object()) else {
+    let Ok(targets) = resolve_agent_auth_targets(Some(openclaw_agents::ALL_AGENTS)) else {
         return false;
     };
+    targets.iter().any(|(_, auth_path)| {
+        let Ok(raw) = fs::read_to_string(auth_path) else {
+            return false;
+        };
+        let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&raw) else {
+            return false;
+        };
+        let Some(profiles) = parsed.get("profiles").and_then(|v| v.as_object()) else {
+            return false;
+        };
 
-    profiles.values().any(|profile| {
-        profile
-            .get("provider")
-            .and_then(|v| v.as_str())
-            .map(|provider| provider == provider_id)
-            .unwrap_or(false)
+        profiles.values().any(|profile| {
+            profile
+                .get("provider")
+                .and_then(|v| v.as_str())
+                .map(|provider| provider == provider_id)
+                .unwrap_or(false)
+        })
     })
 }
 
@@ -1938,7 +2150,7 @@ fn copy_dir_with_native_tool(src: &PathBuf, dst: &PathBuf) -> Result<(), String>
     }
 }
 
-fn resolve_prefix_openclaw_entry(prefix: &PathBuf) -> Option<PathBuf> {
+fn resolve_prefix_openclaw_entry(prefix: &Path) -> Option<PathBuf> {
     let candidates = vec![
         prefix.join("node_modules").join("openclaw").join("openclaw.mjs"),
         prefix
@@ -1950,7 +2162,7 @@ fn resolve_prefix_openclaw_entry(prefix: &PathBuf) -> Option<PathBuf> {
     candidates.into_iter().find(|candidate| candidate.exists())
 }
 
-fn resolve_bundled_node_binary(bundle_dir: &PathBuf) -> Option<PathBuf> {
+fn resolve_bundled_node_binary(bundle_dir: &Path) -> Option<PathBuf> {
     let candidates = if cfg!(target_os = "windows") {
         vec![
             bundle_dir.join("node").join("bin").join("node.exe"),
@@ -1965,7 +2177,7 @@ fn resolve_bundled_node_binary(bundle_dir: &PathBuf) -> Option<PathBuf> {
     candidates.into_iter().find(|candidate| candidate.exists())
 }
 
-fn resolve_node_runtime_root(node_binary: &PathBuf) -> Option<PathBuf> {
+fn resolve_node_runtime_root(node_binary: &Path) -> Option<PathBuf> {
     let parent = node_binary.parent()?;
     let is_bin_dir = parent
         .file_name()
@@ -1979,7 +2191,7 @@ fn resolve_node_runtime_root(node_binary: &PathBuf) -> Option<PathBuf> {
     }
 }
 
-fn resolve_node_binary_in_runtime(runtime_dir: &PathBuf) -> Option<PathBuf> {
+fn resolve_node_binary_in_runtime(runtime_dir: &Path) -> Option<PathBuf> {
     let candidates = if cfg!(target_os = "windows") {
         vec![
             runtime_dir.join("bin").join("node.exe"),
@@ -1992,8 +2204,8 @@ fn resolve_node_binary_in_runtime(runtime_dir: &PathBuf) -> Option<PathBuf> {
 }
 
 fn ensure_prefix_openclaw_launcher(
-    prefix: &PathBuf,
-    bundle_dir: &PathBuf,
+    prefix: &Path,
+    bundle_dir: &Path,
     logs: &mut Vec<String>,
 ) -> Result<(), String> {
     let openclaw_entry = resolve_prefix_openclaw_entry(prefix).ok_or_else(|| {
@@ -2058,7 +2270,7 @@ fn ensure_prefix_openclaw_launcher(
     Ok(())
 }
 
-fn prefix_has_openclaw_binary(prefix: &PathBuf) -> bool {
+fn prefix_has_openclaw_binary(prefix: &Path) -> bool {
     let candidates = if cfg!(target_os = "windows") {
         vec![
             prefix.join("bin").join("openclaw.cmd"),
@@ -2177,67 +2389,214 @@ fn install_openclaw_from_bundle(
     }
 }
 
-fn gateway_child_slot() -> &'static Mutex<Option<Child>> {
-    static SLOT: OnceLock<Mutex<Option<Child>>> = OnceLock::new();
-    SLOT.get_or_init(|| Mutex::new(None))
+#[tauri::command]
+fn get_gateway_supervisor_status() -> Result<gateway_supervisor::GatewaySupervisorStatus, String> {
+    gateway_supervisor::status()
 }
 
-fn is_gateway_process_alive() -> bool {
-    let Ok(mut guard) = gateway_child_slot().lock() else {
-        return false;
-    };
+// Runs blocking work (process waits, CLI calls) off the async runtime's workers.
+async fn run_blocking<T: Send + 'static>(
+    task: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    tauri::async_runtime::spawn_blocking(task)
+        .await
+        .map_err(|err| format!("Background task failed: {}", err))?
+}
 
-    match guard.as_mut() {
-        Some(child) => match child.try_wait() {
-            Ok(None) => true,
-            Ok(Some(_)) | Err(_) => {
-                *guard = None;
-                false
-            }
-        },
-        None => false,
+#[tauri::command]
+async fn stop_gateway() -> Result<gateway_supervisor::GatewayStopResult, String> {
+    run_blocking(gateway_supervisor::stop).await
+}
+
+#[tauri::command]
+async fn restart_gateway() -> Result<gateway_supervisor::GatewaySupervisorStatus, String> {
+    // A reachable gateway the app did not spawn belongs to the OpenClaw daemon;
+    // starting a second one would only fight it for the port.
+    let endpoint = resolve_gateway_endpoint();
+    if !gateway_supervisor::is_running() && probe_gateway_health(&endpoint).await.is_openclaw {
+        return Err(
+            "The running gateway is managed by the OpenClaw service; restart it with `openclaw gateway restart`."
+                .to_string(),
+        );
     }
+    let binary = resolve_openclaw_binary()
+        .ok_or_else(|| "openclaw binary not found. Install OpenClaw first, then retry.".to_string())?;
+    run_blocking(move || gateway_supervisor::restart(&binary, &endpoint)).await
 }
 
-fn spawn_gateway_process(binary: &str) -> Result<bool, String> {
-    let mut guard = gateway_child_slot()
-        .lock()
-        .map_err(|_| "Failed to lock gateway process state".to_string())?;
+#[tauri::command]
+async fn set_gateway_port(port: u16) -> Result<gateway_endpoint::GatewayEndpoint, String> {
+    if port == 0 {
+        return Err("Gateway port must be between 1 and 65535.".to_string());
+    }
+    update_openclaw_config(|config| {
+        config.set_gateway_port(port);
+        Ok(())
+    })?;
+    if profiles::active().is_some() {
+        profiles::set_active_port(port)?;
+    }
 
-    if let Some(child) = guard.as_mut() {
-        match child.try_wait() {
-            Ok(None) => return Ok(false),
-            Ok(Some(_)) | Err(_) => {
-                *guard = None;
-            }
+    let endpoint = resolve_gateway_endpoint();
+    // Move a gateway the app owns onto the new port right away.
+    if gateway_supervisor::is_running() {
+        if let Some(binary) = resolve_openclaw_binary() {
+            let target = endpoint.clone();
+            run_blocking(move || gateway_supervisor::restart(&binary, &target)).await?;
         }
     }
+    Ok(endpoint)
+}
 
-    let child = Command::new(binary)
-        .arg("gateway")
-        .arg("run")
-        .arg("--allow-unconfigured")
-        .arg("--port")
-        .arg("18789")
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()
-        .map_err(|err| format!("Failed to run `openclaw gateway run`: {}", err))?;
+#[tauri::command]
+fn get_gateway_endpoint() -> gateway_endpoint::GatewayEndpoint {
+    resolve_gateway_endpoint()
+}
 
-    *guard = Some(child);
-    Ok(true)
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ProfileSummary {
+    id: String,
+    name: String,
+    state_dir: String,
+    port: u16,
+    active: bool,
+    // The launch profile follows the environment and cannot be edited here.
+    builtin: bool,
 }
 
-async fn is_official_web_ready() -> bool {
-    let client = match reqwest::Client::builder()
-        .timeout(Duration::from_millis(1200))
-        .build()
-    {
-        Ok(client) => client,
-        Err(_) => return false,
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ProfileList {
+    active_id: String,
+    profiles: Vec<ProfileSummary>,
+}
+
+fn launch_gateway_port() -> u16 {
+    gateway_endpoint::resolve(
+        read_config_gateway_port(&resolve_launch_config_path()),
+        gateway_endpoint::GatewayBind::default(),
+    )
+    .port
+}
+
+#[tauri::command]
+fn list_profiles() -> ProfileList {
+    let active_id = profiles::active_id();
+    let mut summaries = vec![ProfileSummary {
+        id: profiles::LAUNCH_PROFILE_ID.to_string(),
+        name: "Default".to_string(),
+        state_dir: resolve_launch_state_dir().to_string_lossy().to_string(),
+        port: launch_gateway_port(),
+        active: active_id == profiles::LAUNCH_PROFILE_ID,
+        builtin: true,
+    }];
+    summaries.extend(profiles::registered().into_iter().map(|profile| ProfileSummary {
+        active: profile.id == active_id,
+        id: profile.id,
+        name: profile.name,
+        state_dir: profile.state_dir,
+        port: profile.port,
+        builtin: false,
+    }));
+    ProfileList {
+        active_id,
+        profiles: summaries,
+    }
+}
+
+#[tauri::command]
+fn create_profile(
+    name: String,
+    state_dir: Option<String>,
+    port: Option<u16>,
+) -> Result<ProfileList, String> {
+    let slug = profiles::slugify(&name);
+    let state_dir = match state_dir.as_deref().map(str::trim).filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => resolve_user_home()
+            .unwrap_or_default()
+            .join(format!(".openclaw-{}", slug)),
     };
 
-    client.get(OFFICIAL_WEB_URL).send().await.is_ok()
+    let launch_port = launch_gateway_port();
+    let port = match port {
+        Some(0) => return Err("Gateway port must be between 1 and 65535.".to_string()),
+        Some(port) => port,
+        None => {
+            let mut taken: BTreeSet<u16> = profiles::registered()
+                .iter()
+                .map(|profile| profile.port)
+                .collect();
+            taken.insert(launch_port);
+            let host = resolve_gateway_endpoint().host;
+            (launch_port.saturating_add(1)..=u16::MAX)
+                .find(|port| {
+                    !taken.contains(port) && gateway_endpoint::is_port_free(&host, *port)
+                })
+                .ok_or_else(|| "No free port is available for the new profile.".to_string())?
+        }
+    };
+    if port == launch_port {
+        return Err(format!("Port {} is used by the default profile.", port));
+    }
+
+    profiles::create(&name, state_dir, port)?;
+    Ok(list_profiles())
+}
+
+#[tauri::command]
+async fn switch_profile(app: tauri::AppHandle, id: String) -> Result<ProfileList, String> {
+    let id = id.trim();
+    if id == profiles::active_id() {
+        return Ok(list_profiles());
+    }
+    if id != profiles::LAUNCH_PROFILE_ID && !profiles::registered().iter().any(|p| p.id == id) {
+        return Err(format!("Unknown profile '{}'.", id));
+    }
+
+    // The gateway belongs to the old profile's state dir and port.
+    let stopped = run_blocking(gateway_supervisor::stop).await?;
+    profiles::set_active(id)?;
+
+    gateway_log::set_dir(resolve_openclaw_state_dir().join("logs"));
+    if let Err(error) = config_watcher::start(
+        app.clone(),
+        resolve_openclaw_config_path(),
+        resolve_openclaw_auth_profiles_path(),
+    ) {
+        gateway_log::note(&format!("Config watcher disabled: {}", error));
+    }
+    if let Err(error) = codex_sync::start(app, resolve_openclaw_state_dir(), "profile-switch") {
+        gateway_log::note(&format!("Codex sync disabled: {}", error));
+    }
+    if stopped.stopped {
+        run_blocking(|| match resolve_openclaw_binary() {
+            Some(binary) => {
+                gateway_supervisor::ensure_running(&binary, &resolve_gateway_endpoint())
+            }
+            None => Ok(false),
+        })
+        .await?;
+    }
+    Ok(list_profiles())
+}
+
+#[tauri::command]
+fn tail_gateway_log(lines: usize, follow: bool) -> Result<gateway_log::GatewayLogTail, String> {
+    gateway_log::tail(lines, follow)
+}
+
+async fn probe_gateway_health(
+    endpoint: &gateway_endpoint::GatewayEndpoint,
+) -> gateway_health::GatewayHealthProbe {
+    let token = read_gateway_auth_token();
+    gateway_health::probe(endpoint, token.as_deref(), gateway_supervisor::is_running()).await
+}
+
+#[tauri::command]
+async fn get_gateway_health() -> gateway_health::GatewayHealthProbe {
+    probe_gateway_health(&resolve_gateway_endpoint()).await
 }
 
 #[tauri::command]
@@ -2253,7 +2612,7 @@ fn list_oauth_providers() -> Vec<String> {
         return providers.into_iter().collect();
     };
 
-    let output = Command::new(&binary)
+    let output = profiles::apply_env(&mut Command::new(&binary))
         .arg("models")
         .arg("status")
         .arg("--json")
@@ -2275,7 +2634,10 @@ fn list_oauth_providers() -> Vec<String> {
         }
     }
 
-    let onboard_help = Command::new(&binary).arg("onboard").arg("--help").output();
+    let onboard_help = profiles::apply_env(&mut Command::new(&binary))
+        .arg("onboard")
+        .arg("--help")
+        .output();
     if let Ok(help) = onboard_help {
         if help.status.success() {
             let text = String::from_utf8_lossy(&help.stdout).to_string();
@@ -2478,21 +2840,180 @@ async fn check_ollama() -> Result<OllamaStatus, String> {
 }
 
 #[tauri::command]
-async fn ensure_official_web_ready() -> OfficialWebStatus {
+async fn validate_api_key(
+    provider_id: String,
+    api_key: String,
+    base_url: Option<String>,
+) -> Result<ApiKeyValidationStatus, String> {
+    let Some(provider_id) = normalize_api_key_provider_id(&provider_id) else {
+        return Err("provider_id is required".to_string());
+    };
+    let api_key = api_key.trim().to_string();
+    if api_key.is_empty() {
+        return Err("api_key is required".to_string());
+    }
+
+    let base_url = base_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or_else(|| resolve_provider_api_base_url(&provider_id))
+        .ok_or_else(|| {
+            format!(
+                "No default endpoint for provider {}; pass base_url to validate it.",
+                provider_id
+            )
+        })?;
+    let endpoint = format!("{}/models", base_url.trim_end_matches('/'));
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|err| format!("Failed to create HTTP client: {}", err))?;
+    let request = if provider_id == "anthropic" {
+        client
+            .get(&endpoint)
+            .header("x-api-key", &api_key)
+            .header("anthropic-version", "2023-06-01")
+    } else {
+        client.get(&endpoint).bearer_auth(&api_key)
+    };
+
+    let response = match request.send().await {
+        Ok(response) => response,
+        Err(err) => {
+            return Ok(ApiKeyValidationStatus {
+                provider_id,
+                endpoint,
+                valid: false,
+                http_status: None,
+                models: vec![],
+                error_kind: Some("network".to_string()),
+                error: Some(err.to_string()),
+            });
+        }
+    };
+
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+        let detail = body.trim();
+        return Ok(ApiKeyValidationStatus {
+            provider_id,
+            endpoint,
+            valid: false,
+            http_status: Some(status.as_u16()),
+            models: vec![],
+            error_kind: Some(classify_api_key_http_error(status.as_u16(), &body).to_string()),
+            error: Some(if detail.is_empty() {
+                format!("HTTP {}", status.as_u16())
+            } else {
+                format!("HTTP {}: {}", status.as_u16(), summarize_output(detail.as_bytes(), &[]))
+            }),
+        });
+    }
+
+    let models = serde_json::from_str::<ProviderModelsResponse>(&body)
+        .ok()
+        .and_then(|parsed| parsed.data)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|item| item.id)
+        .collect::<Vec<_>>();
+
+    Ok(ApiKeyValidationStatus {
+        provider_id,
+        endpoint,
+        valid: true,
+        http_status: Some(status.as_u16()),
+        models,
+        error_kind: None,
+        error: None,
+    })
+}
+
+fn port_conflict_status(
+    endpoint: &gateway_endpoint::GatewayEndpoint,
+    url: String,
+    command_hint: String,
+    health: gateway_health::GatewayHealthProbe,
+) -> OfficialWebStatus {
+    let port = endpoint.port;
+    let suggested_port = gateway_endpoint::next_free_port(&endpoint.host, port);
+    OfficialWebStatus {
+        ready: false,
+        installed: true,
+        running: false,
+        started: false,
+        url,
+        port,
+        suggested_port,
+        state: health.state,
+        health: Some(health),
+        command_hint,
+        message: format!("Port {} is in use by another program.", port),
+        error: Some(match suggested_port {
+            Some(free) => format!(
+                "Free port {} or switch the gateway to port {}.",
+                port, free
+            ),
+            None => format!("Free port {} or set gateway.port to a free port.", port),
+        }),
+    }
+}
+
+#[tauri::command]
+async fn ensure_official_web_ready(
+    app: tauri::AppHandle,
+    timeout_ms: Option<u64>,
+) -> OfficialWebStatus {
     let command_hint = "openclaw gateway".to_string();
-    let url = resolve_official_dashboard_url();
+    let endpoint = resolve_gateway_endpoint();
+    let url = resolve_official_dashboard_url(&endpoint);
+    let port = endpoint.port;
 
-    if is_official_web_ready().await {
-        return OfficialWebStatus {
-            ready: true,
-            installed: true,
-            running: true,
-            started: false,
-            url,
-            command_hint,
-            message: "Official local web is already reachable.".to_string(),
-            error: None,
-        };
+    let health = probe_gateway_health(&endpoint).await;
+    match health.state {
+        gateway_health::GatewayHealthState::Ready => {
+            return OfficialWebStatus {
+                ready: true,
+                installed: true,
+                running: true,
+                started: false,
+                url,
+                port,
+                suggested_port: None,
+                state: health.state,
+                health: Some(health),
+                command_hint,
+                message: "Official local web is already reachable.".to_string(),
+                error: None,
+            };
+        }
+        // Spawning a gateway on a foreign port would only crash-loop.
+        gateway_health::GatewayHealthState::PortInUse => {
+            return port_conflict_status(&endpoint, url, command_hint, health);
+        }
+        // A gateway the app does not own (the daemon) answers but is broken;
+        // a second one cannot take its port, so report it as is.
+        gateway_health::GatewayHealthState::Unhealthy if !gateway_supervisor::is_running() => {
+            return OfficialWebStatus {
+                ready: false,
+                installed: true,
+                running: true,
+                started: false,
+                url,
+                port,
+                suggested_port: None,
+                state: health.state,
+                error: health.error.clone(),
+                health: Some(health),
+                command_hint,
+                message: "Local gateway is running but unhealthy.".to_string(),
+            };
+        }
+        _ => {}
     }
 
     let Some(binary) = resolve_openclaw_binary() else {
@@ -2502,13 +3023,22 @@ async fn ensure_official_web_ready() -> OfficialWebStatus {
             running: false,
             started: false,
             url,
+            port,
+            suggested_port: None,
+            state: health.state,
+            health: Some(health),
             command_hint,
             message: "openclaw binary not found.".to_string(),
             error: Some("Install OpenClaw first, then retry.".to_string()),
         };
     };
 
-    let started = match spawn_gateway_process(&binary) {
+    let start_endpoint = endpoint.clone();
+    let started = match run_blocking(move || {
+        gateway_supervisor::ensure_running(&binary, &start_endpoint)
+    })
+    .await
+    {
         Ok(started) => started,
         Err(error) => {
             return OfficialWebStatus {
@@ -2517,6 +3047,10 @@ async fn ensure_official_web_ready() -> OfficialWebStatus {
                 running: false,
                 started: false,
                 url,
+                port,
+                suggested_port: None,
+                state: health.state,
+                health: Some(health),
                 command_hint,
                 message: "Failed to start local gateway.".to_string(),
                 error: Some(error),
@@ -2524,42 +3058,115 @@ async fn ensure_official_web_ready() -> OfficialWebStatus {
         }
     };
 
-    for _ in 0..30 {
-        if is_official_web_ready().await {
+    let deadline_ms = timeout_ms
+        .unwrap_or(OFFICIAL_WEB_WAIT_DEFAULT_MS)
+        .clamp(OFFICIAL_WEB_POLL_INTERVAL.as_millis() as u64, OFFICIAL_WEB_WAIT_MAX_MS);
+    let deadline = Duration::from_millis(deadline_ms);
+    let wait_started = Instant::now();
+    let wait_id = OFFICIAL_WEB_WAIT_ID.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let mut attempt = 0;
+    let health = loop {
+        attempt += 1;
+        let health = probe_gateway_health(&endpoint).await;
+        match health.state {
+            gateway_health::GatewayHealthState::Ready => {
+                return OfficialWebStatus {
+                    ready: true,
+                    installed: true,
+                    running: true,
+                    started,
+                    url,
+                    port,
+                    suggested_port: None,
+                    state: health.state,
+                    health: Some(health),
+                    command_hint,
+                    message: if started {
+                        "Official local web started successfully."
+                    } else {
+                        "Official local web is reachable."
+                    }
+                    .to_string(),
+                    error: None,
+                };
+            }
+            gateway_health::GatewayHealthState::PortInUse => {
+                return port_conflict_status(&endpoint, url, command_hint, health);
+            }
+            _ => {}
+        }
+
+        let elapsed = wait_started.elapsed();
+        let _ = app.emit(
+            OFFICIAL_WEB_PROGRESS_EVENT,
+            OfficialWebWaitProgress {
+                wait_id,
+                attempt,
+                elapsed_ms: elapsed.as_millis() as u64,
+                deadline_ms,
+                state: health.state,
+            },
+        );
+        if elapsed >= deadline {
+            break health;
+        }
+        tokio::time::sleep(OFFICIAL_WEB_POLL_INTERVAL.min(deadline - elapsed)).await;
+
+        // The gateway keeps starting in the background; only the wait stops.
+        if OFFICIAL_WEB_WAIT_CANCELLED_ID.load(Ordering::SeqCst) == wait_id {
             return OfficialWebStatus {
-                ready: true,
+                ready: false,
                 installed: true,
-                running: true,
+                running: gateway_supervisor::is_running(),
                 started,
                 url,
+                port,
+                suggested_port: None,
+                state: health.state,
+                health: Some(health),
                 command_hint,
-                message: if started {
-                    "Official local web started successfully."
-                } else {
-                    "Official local web is reachable."
-                }
-                .to_string(),
-                error: None,
+                message: "Waiting for the local web was cancelled.".to_string(),
+                error: Some("Cancelled".to_string()),
             };
         }
-        std::thread::sleep(Duration::from_millis(400));
-    }
+    };
 
+    let message = match health.state {
+        gateway_health::GatewayHealthState::Unhealthy => "Gateway started, but it reports errors.",
+        _ => "Gateway started, but local web did not become ready in time.",
+    };
     OfficialWebStatus {
         ready: false,
         installed: true,
-        running: is_gateway_process_alive(),
+        running: gateway_supervisor::is_running(),
         started,
         url,
+        port,
+        suggested_port: None,
+        state: health.state,
+        error: Some(
+            health
+                .error
+                .clone()
+                .unwrap_or_else(|| format!("Timeout while waiting for {}", endpoint.base_url())),
+        ),
+        health: Some(health),
         command_hint,
-        message: "Gateway started, but local web did not become ready in time.".to_string(),
-        error: Some("Timeout while waiting for http://127.0.0.1:18789/".to_string()),
+        message: message.to_string(),
     }
 }
 
+// Without an id the most recent wait is cancelled.
+#[tauri::command]
+fn cancel_official_web_wait(wait_id: Option<u64>) {
+    let wait_id = wait_id.unwrap_or_else(|| OFFICIAL_WEB_WAIT_ID.load(Ordering::SeqCst));
+    OFFICIAL_WEB_WAIT_CANCELLED_ID.store(wait_id, Ordering::SeqCst);
+}
+
 #[tauri::command]
 async fn open_official_web_window(app: tauri::AppHandle) -> Result<OpenOfficialWebResult, String> {
-    let web = ensure_official_web_ready().await;
+    let web = ensure_official_web_ready(app.clone(), None).await;
     if !web.ready {
         let message = [web.error.clone().unwrap_or_default(), web.message]
             .into_iter()
@@ -2631,12 +3238,17 @@ async fn bootstrap_openclaw(app: tauri::AppHandle) -> BootstrapStatus {
         if !installed {
             push_bootstrap_log(&app, &mut logs, "Run online installer...");
             if let Err(error) = run_installer_script(&app, &mut logs) {
+                let endpoint = resolve_gateway_endpoint();
                 let web = OfficialWebStatus {
                     ready: false,
                     installed: false,
                     running: false,
                     started: false,
-                    url: OFFICIAL_WEB_URL.to_string(),
+                    url: endpoint.base_url(),
+                    port: endpoint.port,
+                    suggested_port: None,
+                    state: gateway_health::GatewayHealthState::Down,
+                    health: None,
                     command_hint: "openclaw gateway".to_string(),
                     message: "OpenClaw install failed.".to_string(),
                     error: Some(error.clone()),
@@ -2657,12 +3269,17 @@ async fn bootstrap_openclaw(app: tauri::AppHandle) -> BootstrapStatus {
     }
 
     let Some(binary) = resolve_openclaw_binary() else {
+        let endpoint = resolve_gateway_endpoint();
         let web = OfficialWebStatus {
             ready: false,
             installed: false,
             running: false,
             started: false,
-            url: OFFICIAL_WEB_URL.to_string(),
+            url: endpoint.base_url(),
+            port: endpoint.port,
+            suggested_port: None,
+            state: gateway_health::GatewayHealthState::Down,
+            health: None,
             command_hint: "openclaw gateway".to_string(),
             message: "OpenClaw CLI still not found after install.".to_string(),
             error: Some("Binary not found".to_string()),
@@ -2695,7 +3312,7 @@ async fn bootstrap_openclaw(app: tauri::AppHandle) -> BootstrapStatus {
             push_bootstrap_log(&app, &mut logs, format!("WARN: {}", error));
         }
         let auth_ready = check_models_auth_ready(&app, &binary, &mut logs);
-        let web = ensure_official_web_ready().await;
+        let web = ensure_official_web_ready(app.clone(), None).await;
         if web.ready && auth_ready {
             return BootstrapStatus {
                 ready: true,
@@ -2714,6 +3331,7 @@ async fn bootstrap_openclaw(app: tauri::AppHandle) -> BootstrapStatus {
         );
     }
 
+    snapshot_openclaw_configs(&app, &mut logs, "before openclaw setup/onboard");
     push_bootstrap_log(&app, &mut logs, "Running setup...");
     let setup_ok = match run_openclaw(&app, &binary, &["setup"], &mut logs) {
         Ok(_) => true,
@@ -2765,6 +3383,7 @@ async fn bootstrap_openclaw(app: tauri::AppHandle) -> BootstrapStatus {
             &mut logs,
             "Onboard failed, trying gateway install --force + start...",
         );
+        snapshot_openclaw_configs(&app, &mut logs, "before openclaw gateway install --force");
         let install_ok = match run_openclaw(
             &app,
             &binary,
@@ -2793,7 +3412,7 @@ async fn bootstrap_openclaw(app: tauri::AppHandle) -> BootstrapStatus {
             &mut logs,
             "Local Codex auth detected, syncing into OpenClaw auth-profiles...",
         );
-        match sync_local_codex_auth_to_openclaw(true) {
+        match sync_local_auth_to_openclaw(&local_credentials::CodexSource, true, None) {
             Ok(result) => {
                 push_bootstrap_log(&app, &mut logs, format!("OK: {}", result.message));
                 if let Some(profile_id) = result.profile_id {
@@ -2828,7 +3447,7 @@ async fn bootstrap_openclaw(app: tauri::AppHandle) -> BootstrapStatus {
 
     let model_auth_ready = check_models_auth_ready(&app, &binary, &mut logs);
     let initialized = onboard_ok && model_auth_ready;
-    let web = ensure_official_web_ready().await;
+    let web = ensure_official_web_ready(app.clone(), None).await;
     let ready = installed && initialized && web.ready;
 
     if !setup_ok {
@@ -2868,165 +3487,607 @@ async fn bootstrap_openclaw(app: tauri::AppHandle) -> BootstrapStatus {
 }
 
 #[tauri::command]
-fn reuse_local_codex_auth(set_default_model: Option<bool>) -> LocalCodexReuseResult {
-    match sync_local_codex_auth_to_openclaw(set_default_model.unwrap_or(true)) {
-        Ok(result) => result,
-        Err(error) => LocalCodexReuseResult {
-            reused: false,
-            profile_id: None,
-            model: None,
-            message: "Failed to reuse local Codex auth.".to_string(),
-            error: Some(error),
-        },
+fn get_auth_health() -> Vec<auth_monitor::AuthProfileHealth> {
+    auth_monitor::scan()
+}
+
+#[tauri::command]
+fn list_openclaw_agents() -> Vec<openclaw_agents::OpenClawAgentSummary> {
+    list_openclaw_agent_dirs()
+        .into_iter()
+        .map(|(id, dir)| {
+            let is_default = id == DEFAULT_OPENCLAW_AGENT_ID;
+            openclaw_agents::summarize(&id, &dir, is_default)
+        })
+        .collect()
+}
+
+// A missing file is an empty store; a corrupt one is an error rather than
+// an empty store, so callers never act on profiles they could not see.
+fn read_auth_profiles_value(auth_profiles_path: &Path) -> Result<serde_json::Value, String> {
+    let raw = match fs::read_to_string(auth_profiles_path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(serde_json::json!({}))
+        }
+        Err(err) => {
+            return Err(format!(
+                "Failed to read {}: {}",
+                auth_profiles_path.to_string_lossy(),
+                err
+            ))
+        }
+    };
+    parse_openclaw_config_text(auth_profiles_path, &raw).map_err(|error| error.describe())
+}
+
+// Agents whose auth-profiles.json holds the profile, with its credential.
+fn find_stored_auth_profile(profile_id: &str) -> Result<Vec<(String, serde_json::Value)>, String> {
+    let mut found = Vec::new();
+    for (agent_id, dir) in list_openclaw_agent_dirs() {
+        let value = read_auth_profiles_value(&dir.join(openclaw_agents::AUTH_PROFILES_FILE))?;
+        if let Some(credential) =
+            auth_profiles::profiles(&value).and_then(|profiles| profiles.get(profile_id))
+        {
+            found.push((agent_id, credential.clone()));
+        }
     }
+    Ok(found)
+}
+
+fn resolve_auth_profile_provider(
+    config: &openclaw_config::OpenClawConfig,
+    profile_id: &str,
+) -> Result<String, String> {
+    let configured = config
+        .auth_profile(profile_id)
+        .and_then(|profile| profile.provider.clone());
+    let provider = match configured {
+        Some(provider) => Some(provider),
+        None => find_stored_auth_profile(profile_id)?
+            .into_iter()
+            .find_map(|(_, credential)| {
+                credential
+                    .get("provider")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            }),
+    };
+    provider
+        .filter(|provider| !provider.trim().is_empty())
+        .ok_or_else(|| format!("Auth profile '{}' does not exist.", profile_id))
 }
 
 #[tauri::command]
-fn save_api_key(provider_id: String, api_key: String) -> Result<serde_json::Value, String> {
-    if provider_id.trim().is_empty() {
-        return Err("provider_id is required".to_string());
+fn list_auth_profiles(
+    agent_id: Option<String>,
+) -> Result<Vec<auth_profiles::AuthProfileEntry>, String> {
+    let config = openclaw_config::OpenClawConfig::from_value(load_openclaw_config_value())
+        .unwrap_or_default();
+    let mut entries: Vec<auth_profiles::AuthProfileEntry> = Vec::new();
+    for (id, auth_profiles_path) in resolve_agent_auth_targets(agent_id.as_deref())? {
+        let value = read_auth_profiles_value(&auth_profiles_path)?;
+        for entry in auth_profiles::list(&id, &value, &config) {
+            // Config-only profiles belong to no agent; report them once.
+            let duplicate = !entry.in_auth_profiles
+                && entries
+                    .iter()
+                    .any(|other| !other.in_auth_profiles && other.profile.id == entry.profile.id);
+            if !duplicate {
+                entries.push(entry);
+            }
+        }
     }
-    if api_key.trim().is_empty() {
-        return Err("api_key is required".to_string());
+    Ok(entries)
+}
+
+#[tauri::command]
+fn delete_auth_profile(
+    profile_id: String,
+    agent_id: Option<String>,
+) -> Result<AuthProfileDeleteResult, String> {
+    let profile_id = profile_id.trim().to_string();
+    let targets = resolve_agent_auth_targets(agent_id.as_deref())?;
+    let config = openclaw_config::OpenClawConfig::from_value(load_openclaw_config_value())
+        .unwrap_or_default();
+
+    let mut agent_ids = Vec::new();
+    for (id, auth_profiles_path) in targets {
+        if !auth_profiles_path.exists() {
+            continue;
+        }
+        let credential = update_auth_profiles_at(&auth_profiles_path, |auth_profiles_value| {
+            Ok(auth_profiles::remove_profile(auth_profiles_value, &profile_id))
+        })?;
+        if credential.is_some() {
+            agent_ids.push(id);
+        }
+    }
+    if agent_ids.is_empty() && config.auth_profile(&profile_id).is_none() {
+        return Err(format!("Auth profile '{}' does not exist.", profile_id));
     }
 
-    Ok(serde_json::json!({ "ok": true }))
+    let mut result = AuthProfileDeleteResult {
+        profile_id: profile_id.clone(),
+        agent_ids,
+        config_cleaned: false,
+    };
+    if find_stored_auth_profile(&profile_id)?.is_empty() {
+        update_openclaw_config(|config| {
+            config.remove_auth_profile(&profile_id);
+            Ok(())
+        })?;
+        result.config_cleaned = true;
+    }
+    Ok(result)
 }
 
-fn read_local_codex_auth_status() -> CodexAuthStatus {
-    let path = resolve_codex_auth_path();
-    let source = path.to_string_lossy().to_string();
+#[tauri::command]
+fn rename_auth_profile(profile_id: String, new_id: String) -> Result<String, String> {
+    let profile_id = profile_id.trim().to_string();
+    let config = openclaw_config::OpenClawConfig::from_value(load_openclaw_config_value())
+        .unwrap_or_default();
+    let provider = resolve_auth_profile_provider(&config, &profile_id)?;
+    let new_id = auth_profiles::renamed_profile_id(&provider, &new_id)?;
+    if new_id == profile_id {
+        return Ok(new_id);
+    }
+    if config.auth_profile(&new_id).is_some() || !find_stored_auth_profile(&new_id)?.is_empty() {
+        return Err(format!("Auth profile '{}' already exists.", new_id));
+    }
+
+    for (_, dir) in list_openclaw_agent_dirs() {
+        let auth_profiles_path = dir.join(openclaw_agents::AUTH_PROFILES_FILE);
+        if !auth_profiles_path.exists() {
+            continue;
+        }
+        update_auth_profiles_at(&auth_profiles_path, |auth_profiles_value| {
+            auth_profiles::rename_profile(auth_profiles_value, &profile_id, &new_id);
+            Ok(())
+        })?;
+    }
+    update_openclaw_config(|config| {
+        config.rename_auth_profile(&profile_id, &new_id);
+        Ok(())
+    })?;
+    Ok(new_id)
+}
 
-    let content = match fs::read_to_string(&path) {
-        Ok(text) => text,
-        Err(_) => {
-            return CodexAuthStatus {
-                detected: false,
-                source,
-                last_refresh: None,
-                token_fields: vec![],
+// OpenClaw prefers an agent's own `order.<provider>` over `auth.order`, so
+// every agent store that has one gets the same change; the others keep
+// following the config.
+fn update_store_auth_orders(
+    provider: &str,
+    reorder: impl Fn(&[String]) -> Vec<String>,
+) -> Result<(), String> {
+    for (_, dir) in list_openclaw_agent_dirs() {
+        let auth_profiles_path = dir.join(openclaw_agents::AUTH_PROFILES_FILE);
+        let value = read_auth_profiles_value(&auth_profiles_path)?;
+        if auth_profiles::store_order(&value, provider).is_none() {
+            continue;
+        }
+        update_auth_profiles_at(&auth_profiles_path, |auth_profiles_value| {
+            if let Some(current) = auth_profiles::store_order(auth_profiles_value, provider) {
+                auth_profiles::set_store_order(auth_profiles_value, provider, reorder(&current));
             }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+// Sets `auth.order.<provider>`. Profiles of the provider that were ordered
+// before but are left out keep their relative order after the given ones.
+#[tauri::command]
+fn reorder_auth_profiles(
+    provider_id: String,
+    profile_ids: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let provider_id = provider_id.trim().to_string();
+    let config = openclaw_config::OpenClawConfig::from_value(load_openclaw_config_value())
+        .unwrap_or_default();
+    let mut order: Vec<String> = Vec::new();
+    for id in profile_ids.iter().map(|id| id.trim()).filter(|id| !id.is_empty()) {
+        if order.iter().any(|current| current == id) {
+            return Err(format!("Auth profile '{}' is listed twice.", id));
+        }
+        let provider = resolve_auth_profile_provider(&config, id)?;
+        if provider != provider_id {
+            return Err(format!(
+                "Auth profile '{}' belongs to {}, not {}.",
+                id, provider, provider_id
+            ));
         }
+        order.push(id.to_string());
+    }
+    if order.is_empty() {
+        return Err("profile_ids is required".to_string());
+    }
+    for id in config.auth_order(&provider_id) {
+        if !order.contains(id) {
+            order.push(id.clone());
+        }
+    }
+
+    update_store_auth_orders(&provider_id, |current| {
+        let mut next = order.clone();
+        next.extend(current.iter().filter(|id| !order.contains(id)).cloned());
+        next
+    })?;
+    update_openclaw_config(|config| {
+        config.set_auth_order(&provider_id, order.clone());
+        Ok(order)
+    })
+}
+
+#[tauri::command]
+fn set_primary_auth_profile(profile_id: String) -> Result<Vec<String>, String> {
+    let profile_id = profile_id.trim().to_string();
+    let config = openclaw_config::OpenClawConfig::from_value(load_openclaw_config_value())
+        .unwrap_or_default();
+    let provider = resolve_auth_profile_provider(&config, &profile_id)?;
+    update_store_auth_orders(&provider, |current| {
+        std::iter::once(profile_id.clone())
+            .chain(current.iter().filter(|id| **id != profile_id).cloned())
+            .collect()
+    })?;
+    update_openclaw_config(|config| {
+        config.prepend_auth_order(&provider, &profile_id);
+        Ok(config.auth_order(&provider).to_vec())
+    })
+}
+
+#[tauri::command]
+fn reuse_local_codex_auth(
+    set_default_model: Option<bool>,
+    agent_id: Option<String>,
+) -> LocalAuthReuseResult {
+    reuse_local_auth_with(
+        &local_credentials::CodexSource,
+        set_default_model,
+        agent_id.as_deref(),
+    )
+}
+
+#[tauri::command]
+fn reuse_local_claude_auth(
+    set_default_model: Option<bool>,
+    agent_id: Option<String>,
+) -> LocalAuthReuseResult {
+    reuse_local_auth_with(
+        &local_credentials::ClaudeCodeSource::default(),
+        set_default_model,
+        agent_id.as_deref(),
+    )
+}
+
+#[tauri::command]
+fn reuse_local_gemini_auth(
+    set_default_model: Option<bool>,
+    agent_id: Option<String>,
+) -> LocalAuthReuseResult {
+    reuse_local_auth_with(
+        &local_credentials::GeminiCliSource,
+        set_default_model,
+        agent_id.as_deref(),
+    )
+}
+
+#[tauri::command]
+fn reuse_local_auth(
+    tool_id: String,
+    set_default_model: Option<bool>,
+    agent_id: Option<String>,
+) -> Result<LocalAuthReuseResult, String> {
+    let source = local_credentials::find(&tool_id)
+        .ok_or_else(|| format!("Unknown local login source '{}'.", tool_id))?;
+    Ok(reuse_local_auth_with(
+        source.as_ref(),
+        set_default_model,
+        agent_id.as_deref(),
+    ))
+}
+
+#[tauri::command]
+fn get_codex_sync_status() -> codex_sync::CodexSyncStatus {
+    let state_dir = resolve_openclaw_state_dir();
+    let settings = codex_sync::load_settings(&state_dir);
+    codex_sync::CodexSyncStatus {
+        enabled: settings.enabled,
+        agent_id: settings.agent_id,
+        watching: codex_sync::is_watching(),
+        codex_auth_path: resolve_codex_auth_path().to_string_lossy().to_string(),
+        audit_path: codex_sync::audit_path(&state_dir)
+            .to_string_lossy()
+            .to_string(),
+        last_entry: codex_sync::read_audit(&state_dir, 1).pop(),
+    }
+}
+
+#[tauri::command]
+fn set_codex_sync(
+    app: tauri::AppHandle,
+    enabled: bool,
+    agent_id: Option<String>,
+) -> Result<codex_sync::CodexSyncStatus, String> {
+    let agent_id = agent_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if enabled {
+        resolve_agent_auth_targets(agent_id.as_deref())?;
+    }
+    let state_dir = resolve_openclaw_state_dir();
+    let previous = codex_sync::load_settings(&state_dir);
+    codex_sync::save_settings(
+        &state_dir,
+        &codex_sync::CodexSyncSettings { enabled, agent_id },
+    )?;
+    if let Err(error) = codex_sync::start(app, state_dir.clone(), "enable") {
+        let _ = codex_sync::save_settings(&state_dir, &previous);
+        return Err(error);
+    }
+    Ok(get_codex_sync_status())
+}
+
+#[tauri::command]
+fn list_codex_sync_audit(limit: Option<usize>) -> Vec<codex_sync::CodexSyncAuditEntry> {
+    codex_sync::read_audit(&resolve_openclaw_state_dir(), limit.unwrap_or(100))
+}
+
+#[tauri::command]
+fn save_api_key(
+    provider_id: String,
+    api_key: String,
+    set_default_model: Option<bool>,
+    agent_id: Option<String>,
+) -> Result<ApiKeySaveResult, String> {
+    let Some(provider_id) = normalize_api_key_provider_id(&provider_id) else {
+        return Err("provider_id is required".to_string());
     };
+    let api_key = api_key.trim().to_string();
+    if api_key.is_empty() {
+        return Err("api_key is required".to_string());
+    }
 
-    let value = match serde_json::from_str::<serde_json::Value>(&content) {
-        Ok(v) => v,
-        Err(_) => {
-            return CodexAuthStatus {
-                detected: false,
-                source,
-                last_refresh: None,
-                token_fields: vec![],
+    let profile_id = format!("{}:default", provider_id);
+    let mut credential = serde_json::Map::new();
+    credential.insert("type".to_string(), serde_json::json!("api_key"));
+    credential.insert("provider".to_string(), serde_json::json!(provider_id));
+    credential.insert("key".to_string(), serde_json::json!(api_key));
+    let targets = resolve_agent_auth_targets(agent_id.as_deref())?;
+    for (_, auth_profiles_path) in &targets {
+        upsert_auth_profile_credential(auth_profiles_path, &profile_id, credential.clone())?;
+    }
+
+    let (model, model_switched) = update_openclaw_config(|config| {
+        config.upsert_auth_profile(&profile_id, &provider_id, "api_key", None);
+        config.prepend_auth_order(&provider_id, &profile_id);
+
+        // Only take over the default model when none is configured, unless the caller insists.
+        let current_primary = config.primary_model().map(str::to_string);
+        if let Some(default_model) = resolve_provider_api_key_default_model(&provider_id) {
+            let should_override = match set_default_model {
+                Some(force) => force,
+                None => current_primary.is_none(),
+            };
+            if should_override && current_primary.as_deref() != Some(default_model) {
+                config.set_primary_model(default_model);
+                return Ok((Some(default_model.to_string()), true));
             }
         }
-    };
+        Ok((current_primary, false))
+    })?;
 
-    let last_refresh = value
-        .get("last_refresh")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
-    let token_fields = value
-        .get("tokens")
-        .and_then(|v| v.as_object())
-        .map(|obj| obj.keys().cloned().collect::<Vec<_>>())
-        .unwrap_or_default();
-    let detected = !token_fields.is_empty();
+    Ok(ApiKeySaveResult {
+        ok: true,
+        profile_id,
+        agent_ids: targets.into_iter().map(|(id, _)| id).collect(),
+        model,
+        model_switched,
+    })
+}
 
-    CodexAuthStatus {
-        detected,
-        source,
-        last_refresh,
-        token_fields,
+// Snapshots both OpenClaw config files before the CLI gets a chance to rewrite them.
+fn snapshot_openclaw_configs(app: &tauri::AppHandle, logs: &mut Vec<String>, reason: &str) {
+    let backups_dir = resolve_config_backups_dir();
+    let sources = [
+        (CONFIG_BACKUP_KIND_OPENCLAW, resolve_openclaw_config_path()),
+        (CONFIG_BACKUP_KIND_AUTH_PROFILES, resolve_openclaw_auth_profiles_path()),
+    ];
+    for (kind, source) in sources {
+        let redact = kind == CONFIG_BACKUP_KIND_AUTH_PROFILES;
+        match config_backup::snapshot(&backups_dir, kind, &source, reason, redact) {
+            Ok(Some(entry)) => {
+                push_bootstrap_log(app, logs, format!("Backed up {} as {}", kind, entry.id));
+            }
+            Ok(None) => {}
+            Err(error) => {
+                push_bootstrap_log(app, logs, format!("WARN: failed to back up {}: {}", kind, error));
+            }
+        }
     }
 }
 
 #[tauri::command]
-fn detect_local_codex_auth() -> CodexAuthStatus {
-    read_local_codex_auth_status()
+fn list_config_backups() -> Vec<config_backup::ConfigBackupEntry> {
+    config_backup::list(&resolve_config_backups_dir())
 }
 
 #[tauri::command]
-fn detect_local_oauth_tools() -> Vec<LocalOAuthToolStatus> {
-    let codex = read_local_codex_auth_status();
-    let codex_cli = command_exists("codex", &["--version"]);
-
-    let claude_path = resolve_claude_credentials_path();
-    let claude_file_detected = claude_path.exists();
-    let claude_cli = command_exists("claude", &["--version"])
-        || command_exists("claude-code", &["--version"]);
-    let claude_keychain_detected = if cfg!(target_os = "macos") {
-        Command::new("security")
-            .arg("find-generic-password")
-            .arg("-s")
-            .arg(CLAUDE_KEYCHAIN_SERVICE)
-            .arg("-w")
-            .output()
-            .map(|out| out.status.success())
-            .unwrap_or(false)
+fn diff_config_backup(id: String) -> Result<ConfigBackupDiff, String> {
+    let (backup, content) = config_backup::load(&resolve_config_backups_dir(), id.trim())?;
+    let source = PathBuf::from(&backup.source_path);
+    let current_exists = source.exists();
+    let current = if current_exists {
+        fs::read_to_string(&source)
+            .map_err(|err| format!("Failed to read {}: {}", backup.source_path, err))?
     } else {
-        false
+        String::new()
     };
+    // Compare like with like: auth-profiles backups are stored redacted.
+    let current = if backup.kind == CONFIG_BACKUP_KIND_AUTH_PROFILES && current_exists {
+        config_backup::redact_secrets(&current).unwrap_or(current)
+    } else {
+        current
+    };
+    let diff = config_backup::unified_diff(
+        &content,
+        &current,
+        &format!("backup/{}", backup.id),
+        &backup.source_path,
+    );
 
-    let gemini_cli = command_exists("gemini", &["--version"]);
-    let gemini_auth_probe = if gemini_cli {
-        Command::new("gemini")
-            .arg("--output-format")
-            .arg("json")
-            .arg("ok")
-            .output()
-            .map(|out| out.status.success())
-            .unwrap_or(false)
+    Ok(ConfigBackupDiff {
+        identical: current_exists && diff.is_empty(),
+        backup,
+        current_exists,
+        diff,
+    })
+}
+
+#[tauri::command]
+fn restore_config_backup(id: String) -> Result<config_backup::ConfigBackupEntry, String> {
+    let backups_dir = resolve_config_backups_dir();
+    let (backup, content) = config_backup::load(&backups_dir, id.trim())?;
+    // Backups only ever go back to the files they can be taken of.
+    let source_path = PathBuf::from(&backup.source_path);
+    let allowed = match backup.kind.as_str() {
+        CONFIG_BACKUP_KIND_OPENCLAW => source_path == resolve_openclaw_config_path(),
+        CONFIG_BACKUP_KIND_AUTH_PROFILES => list_openclaw_agent_dirs()
+            .into_iter()
+            .any(|(_, dir)| source_path == dir.join(openclaw_agents::AUTH_PROFILES_FILE)),
+        _ => false,
+    };
+    if !allowed {
+        return Err(format!(
+            "Backup {} targets {}, which is not an OpenClaw config or auth-profiles file.",
+            backup.id, backup.source_path
+        ));
+    }
+
+    let mut txn = config_txn::ConfigTransaction::begin(&source_path)?.backup_to(
+        &backups_dir,
+        &backup.kind,
+        &format!("before restoring {}", backup.id),
+    );
+    let current = txn.read()?;
+    let content = if backup.kind == CONFIG_BACKUP_KIND_AUTH_PROFILES {
+        txn = txn.private();
+        config_backup::restore_secrets(&content, current.as_deref())?
     } else {
-        false
+        content
     };
+    if current.as_deref() != Some(content.as_str()) {
+        txn.commit(&content)?;
+    }
+    Ok(backup)
+}
 
-    vec![
-        LocalOAuthToolStatus {
-            id: "codex".to_string(),
-            label: "OpenAI Codex".to_string(),
-            provider_id: "openai-codex".to_string(),
-            cli_found: codex_cli,
-            auth_detected: codex.detected,
-            source: codex.source,
-            detail: if codex.detected {
-                Some("Detected local Codex auth tokens.".to_string())
-            } else {
-                Some("No local Codex auth token detected.".to_string())
-            },
-        },
-        LocalOAuthToolStatus {
-            id: "claude-code".to_string(),
-            label: "Claude Code".to_string(),
-            provider_id: "anthropic".to_string(),
-            cli_found: claude_cli,
-            auth_detected: claude_file_detected || claude_keychain_detected,
-            source: if claude_keychain_detected && cfg!(target_os = "macos") {
-                "macOS Keychain (Claude Code-credentials)".to_string()
-            } else {
-                claude_path.to_string_lossy().to_string()
-            },
-            detail: if claude_file_detected || claude_keychain_detected {
-                Some("Detected reusable Claude Code credentials.".to_string())
-            } else {
-                Some("No reusable Claude Code credentials found.".to_string())
-            },
-        },
-        LocalOAuthToolStatus {
-            id: "gemini-cli".to_string(),
-            label: "Gemini CLI".to_string(),
-            provider_id: "google-gemini-cli".to_string(),
-            cli_found: gemini_cli,
-            auth_detected: gemini_auth_probe,
-            source: "gemini".to_string(),
-            detail: if gemini_auth_probe {
-                Some("Gemini CLI is installed and auth probe succeeded.".to_string())
-            } else if gemini_cli {
-                Some("Gemini CLI detected; auth state unknown or not ready.".to_string())
-            } else {
-                Some("Gemini CLI is not installed.".to_string())
-            },
-        },
-    ]
+#[tauri::command]
+fn detect_local_codex_auth() -> CodexAuthStatus {
+    use local_credentials::LocalCredentialSource;
+
+    let source = local_credentials::CodexSource;
+    let raw = source.read_credentials().ok();
+    let detected = raw
+        .as_ref()
+        .is_some_and(|raw| source.to_openclaw_profile(raw).is_ok());
+    let raw = raw.unwrap_or_default();
+    CodexAuthStatus {
+        detected,
+        source: resolve_codex_auth_path().to_string_lossy().to_string(),
+        last_refresh: raw
+            .get("last_refresh")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        token_fields: raw
+            .get("tokens")
+            .and_then(|v| v.as_object())
+            .map(|obj| obj.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default(),
+    }
+}
+
+#[tauri::command]
+fn detect_local_oauth_tools() -> Vec<local_credentials::LocalOAuthToolStatus> {
+    local_credentials::registry()
+        .iter()
+        .map(|source| source.detect())
+        .collect()
+}
+
+// An explicit model may omit the provider prefix. Without one, the primary
+// model is used when it belongs to the provider, then the provider's default.
+fn resolve_probe_model(
+    config: &openclaw_config::OpenClawConfig,
+    provider_id: &str,
+    model: Option<&str>,
+) -> Result<String, String> {
+    let prefix = format!("{}/", provider_id);
+    if let Some(model) = model.map(str::trim).filter(|model| !model.is_empty()) {
+        if !model.contains('/') {
+            return Ok(format!("{}{}", prefix, model));
+        }
+        if !model.starts_with(&prefix) {
+            return Err(format!("Model {} does not belong to {}.", model, provider_id));
+        }
+        return Ok(model.to_string());
+    }
+    if let Some(primary) = config
+        .primary_model()
+        .filter(|primary| primary.starts_with(&prefix))
+    {
+        return Ok(primary.to_string());
+    }
+    local_credentials::registry()
+        .iter()
+        .filter(|source| source.provider_id() == provider_id)
+        .find_map(|source| source.default_model())
+        .map(|(model, _)| model)
+        .or_else(|| resolve_provider_default_model(provider_id))
+        .or_else(|| resolve_provider_api_key_default_model(provider_id))
+        .map(str::to_string)
+        .ok_or_else(|| format!("No default model is known for {}; pass a model.", provider_id))
+}
+
+#[tauri::command]
+async fn probe_model(
+    provider_id: String,
+    model: Option<String>,
+) -> Result<model_probe::ModelProbeResult, String> {
+    let provider_id = provider_id.trim().to_string();
+    if provider_id.is_empty() {
+        return Err("provider_id is required".to_string());
+    }
+    let binary =
+        resolve_openclaw_binary().ok_or_else(|| "OpenClaw CLI is not installed.".to_string())?;
+    let config = openclaw_config::OpenClawConfig::from_value(load_openclaw_config_value())
+        .unwrap_or_default();
+    let model = resolve_probe_model(&config, &provider_id, model.as_deref())?;
+
+    let auth_profiles_value = read_auth_profiles_value(&resolve_openclaw_auth_profiles_path())?;
+    let expected_auth_profile_id =
+        auth_profiles::first_in_order(&auth_profiles_value, &config, &provider_id);
+    if expected_auth_profile_id.is_none() {
+        return Err(format!("No auth profile is stored for {}.", provider_id));
+    }
+
+    let health = probe_gateway_health(&resolve_gateway_endpoint()).await;
+    let via_gateway = health.state == gateway_health::GatewayHealthState::Ready;
+    let sessions_dir =
+        openclaw_agents::sessions_dir(&resolve_openclaw_state_dir(), DEFAULT_OPENCLAW_AGENT_ID);
+    run_blocking(move || {
+        Ok(model_probe::run(model_probe::ModelProbeRequest {
+            binary: &binary,
+            agent_id: DEFAULT_OPENCLAW_AGENT_ID,
+            provider_id: &provider_id,
+            model: &model,
+            expected_auth_profile_id,
+            via_gateway,
+            sessions_dir,
+        }))
+    })
+    .await
 }
 
 #[tauri::command]
@@ -3099,12 +4160,38 @@ fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
+        .setup(|app| {
+            let registry = profiles::init(profiles::registry_path(&resolve_launch_state_dir()));
+            gateway_log::init(
+                app.handle().clone(),
+                resolve_openclaw_state_dir().join("logs"),
+            );
+            if let Err(error) = registry {
+                gateway_log::note(&format!("Profile registry ignored: {}", error));
+            }
+            gateway_supervisor::init(app.handle().clone());
+            auth_monitor::start(app.handle().clone());
+            if let Err(error) = config_watcher::start(
+                app.handle().clone(),
+                resolve_openclaw_config_path(),
+                resolve_openclaw_auth_profiles_path(),
+            ) {
+                gateway_log::note(&format!("Config watcher disabled: {}", error));
+            }
+            if let Err(error) =
+                codex_sync::start(app.handle().clone(), resolve_openclaw_state_dir(), "startup")
+            {
+                gateway_log::note(&format!("Codex sync disabled: {}", error));
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             list_oauth_providers,
             start_oauth_login,
             check_ollama,
             bootstrap_openclaw,
             ensure_official_web_ready,
+            cancel_official_web_wait,
             open_official_web_window,
             get_browser_mode_status,
             set_browser_mode,
@@ -3112,11 +4199,182 @@ fn main() {
             prepare_browser_relay,
             diagnose_browser_relay,
             save_api_key,
+            validate_api_key,
+            list_config_backups,
+            diff_config_backup,
+            restore_config_backup,
+            get_openclaw_config_status,
+            validate_openclaw_config,
+            get_gateway_supervisor_status,
+            stop_gateway,
+            restart_gateway,
+            get_gateway_endpoint,
+            get_gateway_health,
+            set_gateway_port,
+            tail_gateway_log,
+            list_profiles,
+            create_profile,
+            switch_profile,
             detect_local_codex_auth,
             reuse_local_codex_auth,
+            reuse_local_claude_auth,
+            reuse_local_gemini_auth,
+            reuse_local_auth,
+            get_codex_sync_status,
+            set_codex_sync,
+            list_codex_sync_audit,
+            list_openclaw_agents,
+            get_auth_health,
+            list_auth_profiles,
+            delete_auth_profile,
+            rename_auth_profile,
+            reorder_auth_profiles,
+            set_primary_auth_profile,
             detect_local_oauth_tools,
-            validate_local_codex_connectivity
+            validate_local_codex_connectivity,
+            probe_model
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            // Only the gateway this app spawned is stopped; a daemon-managed
+            // gateway keeps running after the window closes.
+            if let tauri::RunEvent::Exit = event {
+                let _ = gateway_supervisor::stop();
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    // Answers one request per (status, body) pair and hands back the request heads.
+    fn mock_http_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut heads = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().expect("accept");
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).expect("read request");
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..read]);
+                }
+                heads.push(String::from_utf8_lossy(&request).to_string());
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).expect("write response");
+            }
+            heads
+        });
+        (base_url, handle)
+    }
+
+    fn validate(provider_id: &str, base_url: Option<String>) -> ApiKeyValidationStatus {
+        tauri::async_runtime::block_on(validate_api_key(
+            provider_id.to_string(),
+            "sk-test".to_string(),
+            base_url,
+        ))
+        .expect("validation result")
+    }
+
+    #[test]
+    fn validate_api_key_lists_models_from_base_url() {
+        let (base_url, server) =
+            mock_http_server(vec![(200, r#"{"data":[{"id":"gpt-5"},{"id":"gpt-4.1"}]}"#)]);
+        let status = validate("openai", Some(base_url.clone()));
+
+        assert!(status.valid);
+        assert_eq!(status.http_status, Some(200));
+        assert_eq!(status.endpoint, format!("{}/models", base_url));
+        assert_eq!(status.models, vec!["gpt-5", "gpt-4.1"]);
+        let heads = server.join().unwrap();
+        assert!(heads[0].starts_with("GET /v1/models "));
+        assert!(heads[0]
+            .to_ascii_lowercase()
+            .contains("authorization: bearer sk-test"));
+    }
+
+    #[test]
+    fn validate_api_key_sends_anthropic_headers() {
+        let (base_url, server) =
+            mock_http_server(vec![(200, r#"{"data":[{"id":"claude-sonnet-4-5"}]}"#)]);
+        let status = validate("claude", Some(base_url));
+
+        assert!(status.valid);
+        assert_eq!(status.provider_id, "anthropic");
+        let head = server.join().unwrap().remove(0).to_ascii_lowercase();
+        assert!(head.contains("x-api-key: sk-test"));
+        assert!(head.contains("anthropic-version: 2023-06-01"));
+        assert!(!head.contains("authorization:"));
+    }
+
+    #[test]
+    fn validate_api_key_classifies_http_errors() {
+        let cases = vec![
+            (401, r#"{"error":{"message":"Incorrect API key"}}"#, "invalid_key"),
+            (403, r#"{"error":"forbidden"}"#, "invalid_key"),
+            (429, r#"{"error":"rate limited"}"#, "quota"),
+            (400, r#"{"error":{"code":"insufficient_quota"}}"#, "quota"),
+            (500, "", "unexpected"),
+            (503, "upstream unavailable", "unexpected"),
+        ];
+        let (base_url, server) = mock_http_server(
+            cases
+                .iter()
+                .map(|(status, body, _)| (*status, *body))
+                .collect(),
+        );
+        for (status, _, kind) in &cases {
+            let result = validate("deepseek", Some(base_url.clone()));
+            assert!(!result.valid);
+            assert_eq!(result.http_status, Some(*status));
+            assert_eq!(result.error_kind.as_deref(), Some(*kind), "HTTP {}", status);
+            assert!(result.models.is_empty());
+            assert!(result
+                .error
+                .unwrap()
+                .starts_with(&format!("HTTP {}", status)));
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn validate_api_key_reports_network_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+        drop(listener);
+
+        let result = validate("qwen", Some(base_url));
+        assert!(!result.valid);
+        assert_eq!(result.http_status, None);
+        assert_eq!(result.error_kind.as_deref(), Some("network"));
+    }
+
+    #[test]
+    fn classify_api_key_http_error_prefers_quota_messages() {
+        assert_eq!(classify_api_key_http_error(401, ""), "invalid_key");
+        assert_eq!(classify_api_key_http_error(403, ""), "invalid_key");
+        assert_eq!(classify_api_key_http_error(402, ""), "quota");
+        assert_eq!(
+            classify_api_key_http_error(403, "Your credit balance is too low"),
+            "quota"
+        );
+        assert_eq!(classify_api_key_http_error(502, "bad gateway"), "unexpected");
+    }
 }
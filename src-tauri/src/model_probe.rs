@@ -0,0 +1,368 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+pub const PROBE_EXPECTED: &str = "OPENCLAW_PROBE_OK";
+const PROBE_TIMEOUT_SECS: &str = "90";
+// The agent enforces its own timeout; this only catches a CLI that hangs past it.
+const PROBE_KILL_AFTER: Duration = Duration::from_secs(105);
+const SESSIONS_INDEX_FILE: &str = "sessions.json";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelProbeUsage {
+    pub input: Option<u64>,
+    pub output: Option<u64>,
+    pub total: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelProbeResult {
+    pub ok: bool,
+    pub provider_id: String,
+    pub model: String,
+    // "gateway" when the running gateway served the turn, "local" for an
+    // embedded `openclaw agent --local` run.
+    pub via: String,
+    // The profile OpenClaw reports having used; None when it does not say.
+    pub auth_profile_id: Option<String>,
+    // The first stored profile in `auth.order.<provider>`, which OpenClaw is
+    // expected to try first. A guess, not a report.
+    pub expected_auth_profile_id: Option<String>,
+    pub expected: String,
+    pub response: Option<String>,
+    // Provider/model that actually answered, when OpenClaw reports it.
+    pub answered_by: Option<String>,
+    // Whether `answered_by` belongs to the probed provider; None when
+    // OpenClaw did not report who answered.
+    pub answered_by_provider: Option<bool>,
+    pub latency_ms: u64,
+    pub usage: Option<ModelProbeUsage>,
+    pub error: Option<String>,
+    pub command: String,
+}
+
+pub struct ModelProbeRequest<'a> {
+    pub binary: &'a str,
+    // Passed as `--agent`, so the run uses the agent whose auth order the
+    // expected profile was read from.
+    pub agent_id: &'a str,
+    pub provider_id: &'a str,
+    pub model: &'a str,
+    pub expected_auth_profile_id: Option<String>,
+    pub via_gateway: bool,
+    // Where the agent keeps its sessions; after a local run the probe's
+    // session is removed from it. The gateway owns its session index, so
+    // sessions of gateway runs are left to it.
+    pub sessions_dir: PathBuf,
+}
+
+// `openclaw agent --json` prints the gateway reply as `{ result: { payloads,
+// meta } }` and a local run as `{ payloads, meta }`; either may follow log
+// lines on stdout.
+fn parse_agent_json(stdout: &str) -> Option<Value> {
+    let cleaned = crate::strip_ansi_and_controls(stdout);
+    cleaned.match_indices('{').find_map(|(index, _)| {
+        serde_json::Deserializer::from_str(&cleaned[index..])
+            .into_iter::<Value>()
+            .next()?
+            .ok()
+    })
+}
+
+fn reply_text(parsed: &Value) -> Option<String> {
+    let payloads = parsed
+        .pointer("/result/payloads")
+        .or_else(|| parsed.get("payloads"))?
+        .as_array()?;
+    let text = payloads
+        .iter()
+        .filter_map(|payload| payload.get("text").and_then(Value::as_str))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+fn agent_meta(parsed: &Value) -> Option<&Value> {
+    parsed
+        .pointer("/result/meta/agentMeta")
+        .or_else(|| parsed.pointer("/meta/agentMeta"))
+}
+
+fn usage(meta: &Value) -> Option<ModelProbeUsage> {
+    let usage = meta.get("usage")?;
+    let field = |key: &str| usage.get(key).and_then(Value::as_u64);
+    let input = field("input");
+    let output = field("output");
+    Some(ModelProbeUsage {
+        input,
+        output,
+        total: field("total").or_else(|| Some(input? + output?)),
+    })
+}
+
+fn read_all(mut source: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = source.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+fn joined(reader: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default()
+}
+
+// Runs the CLI, killing it when it outlives `PROBE_KILL_AFTER`.
+fn run_with_deadline(command: &mut Command) -> Result<Output, String> {
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to run openclaw: {}", err))?;
+    let stdout = child.stdout.take().map(read_all);
+    let stderr = child.stderr.take().map(read_all);
+
+    let deadline = Instant::now() + PROBE_KILL_AFTER;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                // The readers are left behind: a grandchild may still hold the pipes.
+                return Err(format!(
+                    "openclaw agent did not finish within {} seconds and was stopped.",
+                    PROBE_KILL_AFTER.as_secs()
+                ));
+            }
+            Err(err) => return Err(format!("Failed to wait for openclaw: {}", err)),
+        }
+    };
+    Ok(Output {
+        status,
+        stdout: joined(stdout),
+        stderr: joined(stderr),
+    })
+}
+
+// Drops the probe's transcript files and its entries in the session index.
+fn remove_session(sessions_dir: &Path, session_id: &str) -> Result<(), String> {
+    if let Ok(entries) = fs::read_dir(sessions_dir) {
+        for entry in entries.filter_map(Result::ok) {
+            let name = entry.file_name();
+            if name.to_string_lossy().starts_with(session_id) {
+                fs::remove_file(entry.path()).map_err(|err| {
+                    format!(
+                        "Failed to remove {}: {}",
+                        entry.path().to_string_lossy(),
+                        err
+                    )
+                })?;
+            }
+        }
+    }
+
+    let index_path = sessions_dir.join(SESSIONS_INDEX_FILE);
+    let Ok(raw) = fs::read_to_string(&index_path) else {
+        return Ok(());
+    };
+    let mut index = serde_json::from_str::<Value>(&raw)
+        .map_err(|err| format!("Failed to parse {}: {}", index_path.to_string_lossy(), err))?;
+    let Some(entries) = index.as_object_mut() else {
+        return Ok(());
+    };
+    let before = entries.len();
+    entries.retain(|_, entry| entry.get("sessionId").and_then(Value::as_str) != Some(session_id));
+    if entries.len() == before {
+        return Ok(());
+    }
+    let text = serde_json::to_string_pretty(&index)
+        .map_err(|err| format!("Failed to serialize session index: {}", err))?;
+    crate::config_txn::write_atomic(&index_path, &text, false)
+}
+
+pub fn run(request: ModelProbeRequest) -> ModelProbeResult {
+    // A throwaway session keeps the inline `/model` directive from changing
+    // the model of any session the user works in.
+    let session_id = format!(
+        "desktop-probe-{}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0)
+    );
+    let message = format!(
+        "/model {}\nReply with exactly: {}",
+        request.model, PROBE_EXPECTED
+    );
+    let mut args = vec![
+        "agent",
+        "--agent",
+        request.agent_id,
+        "--json",
+        "--session-id",
+        &session_id,
+        "--timeout",
+        PROBE_TIMEOUT_SECS,
+    ];
+    if !request.via_gateway {
+        args.push("--local");
+    }
+    let command = format!(
+        "openclaw {} --message \"/model {} ...\"",
+        args.join(" "),
+        request.model
+    );
+    args.push("--message");
+    args.push(&message);
+
+    let mut result = ModelProbeResult {
+        ok: false,
+        provider_id: request.provider_id.to_string(),
+        model: request.model.to_string(),
+        via: if request.via_gateway {
+            "gateway"
+        } else {
+            "local"
+        }
+        .to_string(),
+        auth_profile_id: None,
+        expected_auth_profile_id: request.expected_auth_profile_id,
+        expected: PROBE_EXPECTED.to_string(),
+        response: None,
+        answered_by: None,
+        answered_by_provider: None,
+        latency_ms: 0,
+        usage: None,
+        error: None,
+        command,
+    };
+
+    let started = Instant::now();
+    let output = run_with_deadline(
+        crate::profiles::apply_env(&mut Command::new(request.binary)).args(&args),
+    );
+    result.latency_ms = started.elapsed().as_millis() as u64;
+    if !request.via_gateway {
+        if let Err(error) = remove_session(&request.sessions_dir, &session_id) {
+            crate::gateway_log::note(&format!(
+                "Could not remove probe session {}: {}",
+                session_id, error
+            ));
+        }
+    }
+    let output = match output {
+        Ok(output) => output,
+        Err(error) => {
+            result.error = Some(error);
+            return result;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let Some(parsed) = parse_agent_json(&stdout) else {
+        result.error = Some(if output.status.success() {
+            "openclaw agent did not print a JSON result.".to_string()
+        } else {
+            crate::summarize_output(&output.stdout, &output.stderr)
+        });
+        return result;
+    };
+
+    result.response = reply_text(&parsed);
+    if let Some(meta) = agent_meta(&parsed) {
+        let reported = |key: &str| meta.get(key).and_then(Value::as_str).map(str::to_string);
+        result.answered_by = match (reported("provider"), reported("model")) {
+            (_, Some(model)) if model.contains('/') => Some(model),
+            (Some(provider), Some(model)) => Some(format!("{}/{}", provider, model)),
+            (_, model) => model,
+        };
+        result.usage = usage(meta);
+        result.auth_profile_id = reported("authProfileId");
+    }
+
+    let echoed = result
+        .response
+        .as_deref()
+        .is_some_and(|text| text.contains(PROBE_EXPECTED));
+    result.answered_by_provider = result
+        .answered_by
+        .as_deref()
+        .map(|answered| answered.starts_with(&format!("{}/", request.provider_id)));
+    // An unreported provider is left unknown; only a reported mismatch fails.
+    let wrong_provider = result.answered_by_provider == Some(false);
+    result.ok = output.status.success() && echoed && !wrong_provider;
+    if !result.ok {
+        result.error = Some(if !output.status.success() {
+            crate::summarize_output(&output.stdout, &output.stderr)
+        } else if wrong_provider {
+            format!(
+                "The reply came from {} instead of {}.",
+                result.answered_by.as_deref().unwrap_or("-"),
+                request.provider_id
+            )
+        } else {
+            format!("The reply did not echo {}.", PROBE_EXPECTED)
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_session_drops_transcripts_and_index_entries() {
+        let dir =
+            std::env::temp_dir().join(format!("openclaw-probe-sessions-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("desktop-probe-1.jsonl"), "{}").unwrap();
+        fs::write(dir.join("keep.jsonl"), "{}").unwrap();
+        fs::write(
+            dir.join(SESSIONS_INDEX_FILE),
+            r#"{
+                "agent:main:explicit:desktop-probe-1": { "sessionId": "desktop-probe-1" },
+                "agent:main:main": { "sessionId": "keep" }
+            }"#,
+        )
+        .unwrap();
+
+        remove_session(&dir, "desktop-probe-1").unwrap();
+
+        assert!(!dir.join("desktop-probe-1.jsonl").exists());
+        assert!(dir.join("keep.jsonl").exists());
+        let index: Value =
+            serde_json::from_str(&fs::read_to_string(dir.join(SESSIONS_INDEX_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(
+            index,
+            serde_json::json!({ "agent:main:main": { "sessionId": "keep" } })
+        );
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn remove_session_without_sessions_dir_is_a_no_op() {
+        let dir =
+            std::env::temp_dir().join(format!("openclaw-probe-missing-{}", std::process::id()));
+        remove_session(&dir, "desktop-probe-1").unwrap();
+    }
+}
@@ -0,0 +1,109 @@
+use crate::util::string_field;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const AUTH_PROFILES_FILE: &str = "auth-profiles.json";
+// Accepted by agent commands in place of an id to target every agent.
+pub const ALL_AGENTS: &str = "*";
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentAuthProfileSummary {
+    pub id: String,
+    pub provider: Option<String>,
+    // Credential type as stored: "oauth", "api_key" or "token".
+    pub kind: Option<String>,
+    pub email: Option<String>,
+    pub expires_at_ms: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenClawAgentSummary {
+    pub id: String,
+    pub agent_dir: String,
+    pub auth_profiles_path: String,
+    pub is_default: bool,
+    pub profiles: Vec<AgentAuthProfileSummary>,
+    pub error: Option<String>,
+}
+
+// Agent ids become directory names, so keep them to a safe character set.
+pub fn is_valid_agent_id(id: &str) -> bool {
+    !id.is_empty()
+        && id != "."
+        && id != ".."
+        && id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+}
+
+pub fn agent_dir(state_dir: &Path, agent_id: &str) -> PathBuf {
+    state_dir.join("agents").join(agent_id).join("agent")
+}
+
+pub fn sessions_dir(state_dir: &Path, agent_id: &str) -> PathBuf {
+    state_dir.join("agents").join(agent_id).join("sessions")
+}
+
+// Ids of every `agents/<id>/agent` directory under the state dir, sorted.
+pub fn discover(state_dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(state_dir.join("agents")) else {
+        return Vec::new();
+    };
+    let mut ids: Vec<String> = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join("agent").is_dir())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|id| is_valid_agent_id(id))
+        .collect();
+    ids.sort();
+    ids
+}
+
+pub fn summarize_profile(id: &str, credential: &Value) -> AgentAuthProfileSummary {
+    AgentAuthProfileSummary {
+        id: id.to_string(),
+        provider: string_field(credential, "provider"),
+        kind: string_field(credential, "type"),
+        email: string_field(credential, "email"),
+        expires_at_ms: credential.get("expires").and_then(Value::as_i64),
+    }
+}
+
+pub fn summarize(agent_id: &str, agent_dir: &Path, is_default: bool) -> OpenClawAgentSummary {
+    let auth_profiles_path = agent_dir.join(AUTH_PROFILES_FILE);
+    let mut summary = OpenClawAgentSummary {
+        id: agent_id.to_string(),
+        agent_dir: agent_dir.to_string_lossy().to_string(),
+        auth_profiles_path: auth_profiles_path.to_string_lossy().to_string(),
+        is_default,
+        profiles: Vec::new(),
+        error: None,
+    };
+
+    let raw = match fs::read_to_string(&auth_profiles_path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return summary,
+        Err(err) => {
+            summary.error = Some(format!("Failed to read auth profiles: {}", err));
+            return summary;
+        }
+    };
+    let parsed = match serde_json::from_str::<Value>(&raw) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            summary.error = Some(format!("Invalid auth profiles: {}", err));
+            return summary;
+        }
+    };
+    if let Some(profiles) = parsed.get("profiles").and_then(Value::as_object) {
+        summary.profiles = profiles
+            .iter()
+            .map(|(id, credential)| summarize_profile(id, credential))
+            .collect();
+    }
+    summary
+}
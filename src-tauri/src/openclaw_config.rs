@@ -0,0 +1,558 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+// Typed view of the parts of openclaw.json the desktop app reads and writes.
+// Everything else is carried through `extra` untouched, and absent sections
+// stay absent on the way back out, so a load/save round trip is lossless.
+//
+// Loading never fails on a member of an unexpected shape (a quoted port, a
+// null model, ...): such members stay in `extra` and are written back as they
+// were. `extra` is serialized first, so a typed field the app sets replaces
+// the raw member of the same name.
+#[derive(Serialize, Clone, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenClawConfig {
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<AuthConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agents: Option<AgentsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browser: Option<BrowserConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gateway: Option<GatewayConfig>,
+}
+
+#[derive(Serialize, Clone, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthConfig {
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profiles: Option<BTreeMap<String, Lenient<AuthProfileConfig>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<BTreeMap<String, Lenient<Vec<String>>>>,
+}
+
+#[derive(Serialize, Clone, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthProfileConfig {
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+#[derive(Serialize, Clone, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentsConfig {
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defaults: Option<AgentDefaultsConfig>,
+}
+
+#[derive(Serialize, Clone, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentDefaultsConfig {
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<ModelConfig>,
+}
+
+// `agents.defaults.model` is either a bare model id or `{ primary, fallbacks, .. }`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ModelConfig {
+    Name(String),
+    Detailed(ModelSelection),
+}
+
+#[derive(Serialize, Clone, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelSelection {
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary: Option<String>,
+}
+
+#[derive(Serialize, Clone, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowserConfig {
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executable_path: Option<String>,
+}
+
+#[derive(Serialize, Clone, Default, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayConfig {
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+}
+
+// A map entry that is kept verbatim when it does not have the expected shape.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum Lenient<T> {
+    Parsed(T),
+    Raw(Value),
+}
+
+impl<T> Lenient<T> {
+    pub fn parsed(&self) -> Option<&T> {
+        match self {
+            Lenient::Parsed(value) => Some(value),
+            Lenient::Raw(_) => None,
+        }
+    }
+}
+
+// Moves `key` out of `map` when it holds a `T`; nulls and members of another
+// shape are left in place.
+fn take<T: DeserializeOwned>(map: &mut Map<String, Value>, key: &str) -> Option<T> {
+    let value = map.get(key).filter(|value| !value.is_null())?;
+    let parsed = T::deserialize(value).ok()?;
+    map.remove(key);
+    Some(parsed)
+}
+
+impl<'de> Deserialize<'de> for OpenClawConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut extra = Map::deserialize(deserializer)?;
+        Ok(Self {
+            auth: take(&mut extra, "auth"),
+            agents: take(&mut extra, "agents"),
+            browser: take(&mut extra, "browser"),
+            gateway: take(&mut extra, "gateway"),
+            extra,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for AuthConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut extra = Map::deserialize(deserializer)?;
+        Ok(Self {
+            profiles: take(&mut extra, "profiles"),
+            order: take(&mut extra, "order"),
+            extra,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for AuthProfileConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut extra = Map::deserialize(deserializer)?;
+        Ok(Self {
+            provider: take(&mut extra, "provider"),
+            mode: take(&mut extra, "mode"),
+            email: take(&mut extra, "email"),
+            extra,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for AgentsConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut extra = Map::deserialize(deserializer)?;
+        Ok(Self {
+            defaults: take(&mut extra, "defaults"),
+            extra,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for AgentDefaultsConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut extra = Map::deserialize(deserializer)?;
+        Ok(Self {
+            model: take(&mut extra, "model"),
+            extra,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ModelSelection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut extra = Map::deserialize(deserializer)?;
+        Ok(Self {
+            primary: take(&mut extra, "primary"),
+            extra,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for BrowserConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut extra = Map::deserialize(deserializer)?;
+        Ok(Self {
+            enabled: take(&mut extra, "enabled"),
+            default_profile: take(&mut extra, "defaultProfile"),
+            executable_path: take(&mut extra, "executablePath"),
+            extra,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for GatewayConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut extra = Map::deserialize(deserializer)?;
+        Ok(Self {
+            port: take(&mut extra, "port"),
+            extra,
+        })
+    }
+}
+
+fn non_empty(value: Option<&String>) -> Option<&str> {
+    value
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+}
+
+impl OpenClawConfig {
+    pub fn from_value(value: Value) -> Result<Self, String> {
+        serde_json::from_value(value)
+            .map_err(|err| format!("OpenClaw config has an unexpected shape: {}", err))
+    }
+
+    pub fn to_value(&self) -> Result<Value, String> {
+        serde_json::to_value(self)
+            .map_err(|err| format!("Failed to serialize OpenClaw config: {}", err))
+    }
+
+    pub fn primary_model(&self) -> Option<&str> {
+        let model = self.agents.as_ref()?.defaults.as_ref()?.model.as_ref()?;
+        match model {
+            ModelConfig::Name(name) => non_empty(Some(name)),
+            ModelConfig::Detailed(selection) => non_empty(selection.primary.as_ref()),
+        }
+    }
+
+    // A bare model id is upgraded to the object form; other selection fields
+    // (fallbacks etc.) are kept.
+    pub fn set_primary_model(&mut self, model: &str) {
+        let defaults = self
+            .agents
+            .get_or_insert_with(Default::default)
+            .defaults
+            .get_or_insert_with(Default::default);
+        match defaults.model.as_mut() {
+            Some(ModelConfig::Detailed(selection)) => {
+                selection.primary = Some(model.to_string());
+            }
+            _ => {
+                defaults.model = Some(ModelConfig::Detailed(ModelSelection {
+                    primary: Some(model.to_string()),
+                    extra: Map::new(),
+                }));
+            }
+        }
+    }
+
+    pub fn upsert_auth_profile(
+        &mut self,
+        profile_id: &str,
+        provider_id: &str,
+        mode: &str,
+        email: Option<&str>,
+    ) {
+        let profile = self
+            .auth
+            .get_or_insert_with(Default::default)
+            .profiles
+            .get_or_insert_with(Default::default)
+            .entry(profile_id.to_string())
+            .or_insert_with(|| Lenient::Parsed(Default::default()));
+        if let Lenient::Raw(_) = profile {
+            *profile = Lenient::Parsed(Default::default());
+        }
+        let Lenient::Parsed(profile) = profile else {
+            return;
+        };
+        profile.provider = Some(provider_id.to_string());
+        profile.mode = Some(mode.to_string());
+        profile.email = email.map(str::to_string);
+    }
+
+    // Puts `profile_id` first in `auth.order.<provider>`, dropping blanks and duplicates.
+    pub fn prepend_auth_order(&mut self, provider_id: &str, profile_id: &str) {
+        let mut next_order = vec![profile_id.to_string()];
+        for id in self.auth_order(provider_id).iter().map(|id| id.trim()) {
+            if !id.is_empty() && !next_order.iter().any(|current| current == id) {
+                next_order.push(id.to_string());
+            }
+        }
+        self.set_auth_order(provider_id, next_order);
+    }
+
+    pub fn auth_profile(&self, profile_id: &str) -> Option<&AuthProfileConfig> {
+        self.auth
+            .as_ref()?
+            .profiles
+            .as_ref()?
+            .get(profile_id)?
+            .parsed()
+    }
+
+    pub fn auth_order(&self, provider_id: &str) -> &[String] {
+        self.auth
+            .as_ref()
+            .and_then(|auth| auth.order.as_ref())
+            .and_then(|order| order.get(provider_id))
+            .and_then(Lenient::parsed)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn set_auth_order(&mut self, provider_id: &str, profile_ids: Vec<String>) {
+        self.auth
+            .get_or_insert_with(Default::default)
+            .order
+            .get_or_insert_with(Default::default)
+            .insert(provider_id.to_string(), Lenient::Parsed(profile_ids));
+    }
+
+    // Drops the profile's metadata and every `auth.order` mention of it.
+    // Providers left with an empty order lose their entry.
+    pub fn remove_auth_profile(&mut self, profile_id: &str) {
+        let Some(auth) = self.auth.as_mut() else {
+            return;
+        };
+        if let Some(profiles) = auth.profiles.as_mut() {
+            profiles.remove(profile_id);
+        }
+        if let Some(order) = auth.order.as_mut() {
+            order.retain(|_, ids| match ids {
+                Lenient::Parsed(ids) => {
+                    ids.retain(|id| id != profile_id);
+                    !ids.is_empty()
+                }
+                Lenient::Raw(_) => true,
+            });
+        }
+    }
+
+    pub fn rename_auth_profile(&mut self, profile_id: &str, new_id: &str) {
+        let Some(auth) = self.auth.as_mut() else {
+            return;
+        };
+        if let Some(profiles) = auth.profiles.as_mut() {
+            if let Some(profile) = profiles.remove(profile_id) {
+                profiles.insert(new_id.to_string(), profile);
+            }
+        }
+        if let Some(order) = auth.order.as_mut() {
+            let ids = order.values_mut().filter_map(|ids| match ids {
+                Lenient::Parsed(ids) => Some(ids),
+                Lenient::Raw(_) => None,
+            });
+            for id in ids.flatten() {
+                if id == profile_id {
+                    *id = new_id.to_string();
+                }
+            }
+        }
+    }
+
+    pub fn browser_mut(&mut self) -> &mut BrowserConfig {
+        self.browser.get_or_insert_with(Default::default)
+    }
+
+    pub fn browser_default_profile(&self) -> Option<&str> {
+        non_empty(self.browser.as_ref()?.default_profile.as_ref())
+    }
+
+    pub fn browser_executable_path(&self) -> Option<&str> {
+        non_empty(self.browser.as_ref()?.executable_path.as_ref())
+    }
+
+    pub fn set_gateway_port(&mut self, port: u16) {
+        self.gateway.get_or_insert_with(Default::default).port = Some(port);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn load(value: Value) -> OpenClawConfig {
+        OpenClawConfig::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn primary_model_reads_string_and_object_forms() {
+        let named = load(json!({ "agents": { "defaults": { "model": "openai/gpt-5" } } }));
+        assert_eq!(named.primary_model(), Some("openai/gpt-5"));
+
+        let detailed = load(json!({
+            "agents": { "defaults": { "model": { "primary": "anthropic/claude-sonnet-4-5" } } }
+        }));
+        assert_eq!(
+            detailed.primary_model(),
+            Some("anthropic/claude-sonnet-4-5")
+        );
+
+        let blank = load(json!({ "agents": { "defaults": { "model": "  " } } }));
+        assert_eq!(blank.primary_model(), None);
+        assert_eq!(load(json!({})).primary_model(), None);
+    }
+
+    #[test]
+    fn set_primary_model_upgrades_the_string_form() {
+        let mut config = load(json!({
+            "agents": { "defaults": { "model": "openai/gpt-5", "workspace": "~/work" } }
+        }));
+        config.set_primary_model("anthropic/claude-sonnet-4-5");
+        assert_eq!(
+            config.to_value().unwrap(),
+            json!({
+                "agents": {
+                    "defaults": {
+                        "model": { "primary": "anthropic/claude-sonnet-4-5" },
+                        "workspace": "~/work"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn set_primary_model_keeps_fallbacks_of_the_object_form() {
+        let mut config = load(json!({
+            "agents": {
+                "defaults": {
+                    "model": { "primary": "openai/gpt-5", "fallbacks": ["deepseek/deepseek-chat"] }
+                }
+            }
+        }));
+        config.set_primary_model("openai-codex/gpt-5.3-codex");
+        assert_eq!(
+            config.to_value().unwrap(),
+            json!({
+                "agents": {
+                    "defaults": {
+                        "model": {
+                            "primary": "openai-codex/gpt-5.3-codex",
+                            "fallbacks": ["deepseek/deepseek-chat"]
+                        }
+                    }
+                }
+            })
+        );
+
+        let mut empty = load(json!({}));
+        empty.set_primary_model("openai/gpt-5");
+        assert_eq!(empty.primary_model(), Some("openai/gpt-5"));
+    }
+
+    #[test]
+    fn unknown_keys_round_trip() {
+        let value = json!({
+            "meta": { "lastTouchedVersion": "2026.2.1" },
+            "auth": {
+                "profiles": {
+                    "openai:default": { "provider": "openai", "mode": "api_key", "note": "x" }
+                },
+                "order": { "openai": ["openai:default"] },
+                "cooldowns": { "billingBackoffHours": 5 }
+            },
+            "agents": { "defaults": { "model": "openai/gpt-5" }, "list": [{ "id": "main" }] },
+            "browser": { "enabled": true, "profiles": { "openclaw": { "cdpPort": 18800 } } },
+            "gateway": { "port": 18789, "bind": "loopback", "auth": { "token": "t" } },
+            "channels": { "telegram": { "enabled": false } }
+        });
+        assert_eq!(load(value.clone()).to_value().unwrap(), value);
+    }
+
+    #[test]
+    fn members_of_an_unexpected_shape_are_kept() {
+        let value = json!({
+            "auth": {
+                "profiles": { "broken": "not-an-object", "ok:default": { "provider": 7 } },
+                "order": { "openai": "openai:default", "anthropic": ["anthropic:default"] }
+            },
+            "agents": { "defaults": { "model": null } },
+            "browser": "yes",
+            "gateway": { "port": "18789" }
+        });
+        let config = load(value.clone());
+        assert_eq!(config.to_value().unwrap(), value);
+        assert_eq!(config.primary_model(), None);
+        assert_eq!(config.auth_order("openai"), &[] as &[String]);
+        assert_eq!(config.auth_order("anthropic"), ["anthropic:default"]);
+        assert!(config.auth_profile("broken").is_none());
+        assert_eq!(config.auth_profile("ok:default").unwrap().provider, None);
+        assert_eq!(config.gateway.as_ref().unwrap().port, None);
+    }
+
+    #[test]
+    fn typed_writes_replace_members_of_an_unexpected_shape() {
+        let mut config = load(json!({
+            "auth": { "order": { "openai": "openai:old", "anthropic": ["anthropic:default"] } },
+            "agents": { "defaults": { "model": null } },
+            "browser": "yes",
+            "gateway": { "port": "18789", "bind": "loopback" }
+        }));
+        config.prepend_auth_order("openai", "openai:default");
+        config.set_primary_model("openai/gpt-5");
+        config.browser_mut().enabled = Some(true);
+        config.set_gateway_port(19001);
+        assert_eq!(
+            config.to_value().unwrap(),
+            json!({
+                "auth": {
+                    "order": {
+                        "openai": ["openai:default"],
+                        "anthropic": ["anthropic:default"]
+                    }
+                },
+                "agents": { "defaults": { "model": { "primary": "openai/gpt-5" } } },
+                "browser": { "enabled": true },
+                "gateway": { "port": 19001, "bind": "loopback" }
+            })
+        );
+    }
+
+    #[test]
+    fn removing_and_renaming_profiles_updates_the_order() {
+        let mut config = load(json!({
+            "auth": {
+                "profiles": {
+                    "openai:a": { "provider": "openai", "mode": "api_key" },
+                    "openai:b": { "provider": "openai", "mode": "api_key" }
+                },
+                "order": { "openai": ["openai:a", "openai:b"], "legacy": 3 }
+            }
+        }));
+        config.rename_auth_profile("openai:a", "openai:work");
+        config.remove_auth_profile("openai:b");
+        assert_eq!(
+            config.to_value().unwrap(),
+            json!({
+                "auth": {
+                    "profiles": { "openai:work": { "provider": "openai", "mode": "api_key" } },
+                    "order": { "openai": ["openai:work"], "legacy": 3 }
+                }
+            })
+        );
+    }
+}
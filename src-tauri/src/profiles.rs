@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Mutex, OnceLock};
+
+// The profile that follows the environment the app was launched with.
+pub const LAUNCH_PROFILE_ID: &str = "default";
+const REGISTRY_FILE: &str = "desktop-profiles.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopProfile {
+    pub id: String,
+    pub name: String,
+    pub state_dir: String,
+    pub port: u16,
+    pub created_at_ms: u64,
+}
+
+impl DesktopProfile {
+    pub fn state_dir(&self) -> PathBuf {
+        PathBuf::from(&self.state_dir)
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.state_dir().join("openclaw.json")
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+struct ProfileRegistry {
+    #[serde(default)]
+    active: Option<String>,
+    #[serde(default)]
+    profiles: Vec<DesktopProfile>,
+}
+
+struct ProfilesState {
+    path: PathBuf,
+    registry: ProfileRegistry,
+}
+
+fn state_slot() -> &'static Mutex<Option<ProfilesState>> {
+    static STATE: OnceLock<Mutex<Option<ProfilesState>>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(None))
+}
+
+pub fn registry_path(openclaw_home: &Path) -> PathBuf {
+    openclaw_home.join(REGISTRY_FILE)
+}
+
+fn load_registry(path: &Path) -> Result<ProfileRegistry, String> {
+    match fs::read_to_string(path) {
+        Ok(raw) => serde_json::from_str(&raw)
+            .map_err(|err| format!("Failed to parse {}: {}", path.to_string_lossy(), err)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(ProfileRegistry::default()),
+        Err(err) => Err(format!(
+            "Failed to read {}: {}",
+            path.to_string_lossy(),
+            err
+        )),
+    }
+}
+
+fn save_registry(path: &Path, registry: &ProfileRegistry) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {}", parent.to_string_lossy(), err))?;
+    }
+    crate::config_txn::write_atomic(
+        path,
+        &serde_json::to_string_pretty(registry)
+            .map_err(|err| format!("Failed to serialize profile registry: {}", err))?,
+        false,
+    )
+}
+
+// Loads the registry at startup. A broken registry falls back to the launch
+// profile rather than blocking the app.
+pub fn init(path: PathBuf) -> Result<(), String> {
+    let (registry, result) = match load_registry(&path) {
+        Ok(registry) => (registry, Ok(())),
+        Err(error) => (ProfileRegistry::default(), Err(error)),
+    };
+    let mut slot = state_slot()
+        .lock()
+        .map_err(|_| "Profile registry lock poisoned".to_string())?;
+    *slot = Some(ProfilesState { path, registry });
+    result
+}
+
+fn with_state<T>(apply: impl FnOnce(&mut ProfilesState) -> Result<T, String>) -> Result<T, String> {
+    let mut slot = state_slot()
+        .lock()
+        .map_err(|_| "Profile registry lock poisoned".to_string())?;
+    let state = slot
+        .as_mut()
+        .ok_or_else(|| "Profile registry is not initialized".to_string())?;
+    apply(state)
+}
+
+// The registered profile currently in use, or None for the launch profile.
+pub fn active() -> Option<DesktopProfile> {
+    let slot = state_slot().lock().ok()?;
+    let registry = &slot.as_ref()?.registry;
+    let active_id = registry.active.as_deref()?;
+    registry
+        .profiles
+        .iter()
+        .find(|profile| profile.id == active_id)
+        .cloned()
+}
+
+pub fn active_id() -> String {
+    active()
+        .map(|profile| profile.id)
+        .unwrap_or_else(|| LAUNCH_PROFILE_ID.to_string())
+}
+
+pub fn registered() -> Vec<DesktopProfile> {
+    with_state(|state| Ok(state.registry.profiles.clone())).unwrap_or_default()
+}
+
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for ch in name.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+pub fn create(name: &str, state_dir: PathBuf, port: u16) -> Result<DesktopProfile, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name is required.".to_string());
+    }
+    let id = slugify(name);
+    if id.is_empty() || id == LAUNCH_PROFILE_ID {
+        return Err(format!("'{}' cannot be used as a profile name.", name));
+    }
+
+    with_state(|state| {
+        let profiles = &state.registry.profiles;
+        if profiles.iter().any(|profile| profile.id == id) {
+            return Err(format!("A profile named '{}' already exists.", name));
+        }
+        if let Some(other) = profiles
+            .iter()
+            .find(|profile| profile.state_dir() == state_dir)
+        {
+            return Err(format!(
+                "Profile '{}' already uses {}.",
+                other.name,
+                state_dir.to_string_lossy()
+            ));
+        }
+        if let Some(other) = profiles.iter().find(|profile| profile.port == port) {
+            return Err(format!(
+                "Profile '{}' already uses port {}.",
+                other.name, port
+            ));
+        }
+
+        fs::create_dir_all(&state_dir).map_err(|err| {
+            format!(
+                "Failed to create state dir {}: {}",
+                state_dir.to_string_lossy(),
+                err
+            )
+        })?;
+        let profile = DesktopProfile {
+            id: id.clone(),
+            name: name.to_string(),
+            state_dir: state_dir.to_string_lossy().to_string(),
+            port,
+            created_at_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or(0),
+        };
+        let mut next = state.registry.clone();
+        next.profiles.push(profile.clone());
+        save_registry(&state.path, &next)?;
+        state.registry = next;
+        Ok(profile)
+    })
+}
+
+pub fn set_active(id: &str) -> Result<(), String> {
+    with_state(|state| {
+        let active = if id == LAUNCH_PROFILE_ID {
+            None
+        } else if state
+            .registry
+            .profiles
+            .iter()
+            .any(|profile| profile.id == id)
+        {
+            Some(id.to_string())
+        } else {
+            return Err(format!("Unknown profile '{}'.", id));
+        };
+        let mut next = state.registry.clone();
+        next.active = active;
+        save_registry(&state.path, &next)?;
+        state.registry = next;
+        Ok(())
+    })
+}
+
+pub fn set_active_port(port: u16) -> Result<(), String> {
+    with_state(|state| {
+        let mut next = state.registry.clone();
+        let active_id = next.active.clone();
+        let Some(profile) = next
+            .profiles
+            .iter_mut()
+            .find(|profile| Some(&profile.id) == active_id.as_ref())
+        else {
+            return Ok(());
+        };
+        profile.port = port;
+        save_registry(&state.path, &next)?;
+        state.registry = next;
+        Ok(())
+    })
+}
+
+// Points an OpenClaw CLI invocation at the active profile. The launch profile
+// leaves the inherited environment alone.
+pub fn apply_env(command: &mut Command) -> &mut Command {
+    if let Some(profile) = active() {
+        command
+            .env("OPENCLAW_STATE_DIR", profile.state_dir())
+            .env("OPENCLAW_CONFIG_PATH", profile.config_path())
+            .env("OPENCLAW_GATEWAY_PORT", profile.port.to_string())
+            .env_remove("OPENCLAW_AGENT_DIR")
+            .env_remove("PI_CODING_AGENT_DIR");
+    }
+    command
+}
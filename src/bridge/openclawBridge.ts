@@ -1,18 +1,37 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  ApiKeySaveResult,
+  ApiKeyValidationStatus,
   BrowserRelayDiagnostic,
   BrowserRelayStatus,
   BrowserModeStatus,
   BootstrapStatus,
   CodexConnectivityStatus,
   CodexAuthStatus,
-  LocalCodexReuseResult,
+  LocalAuthReuseResult,
   LocalOAuthToolStatus,
   OpenOfficialWebResult,
   OfficialWebStatus,
   OAuthLoginResult,
   OAuthProvider,
   OllamaStatus,
+  ConfigBackupDiff,
+  ConfigBackupEntry,
+  OpenClawConfigStatus,
+  ConfigValidationReport,
+  GatewaySupervisorStatus,
+  GatewayLogTail,
+  GatewayStopResult,
+  GatewayEndpoint,
+  GatewayHealthProbe,
+  ProfileList,
+  OpenClawAgentSummary,
+  AuthProfileHealth,
+  CodexSyncAuditEntry,
+  CodexSyncStatus,
+  AuthProfileDeleteResult,
+  AuthProfileEntry,
+  ModelProbeResult,
   OpenClawBridge
 } from "./types";
 
@@ -33,7 +52,7 @@ const fallbackProviderLabelMap = new Map(fallbackProviders.map((provider) => [pr
 const fallbackLocalTools: LocalOAuthToolStatus[] = [
   {
     id: "codex",
-    label: "OpenAI Codex",
+    label: "Codex",
     providerId: "openai-codex",
     cliFound: false,
     authDetected: false,
@@ -188,7 +207,7 @@ export const openclawBridge: OpenClawBridge = {
     return invoke<BootstrapStatus>("bootstrap_openclaw");
   },
 
-  async ensureOfficialWebReady() {
+  async ensureOfficialWebReady(timeoutMs?: number) {
     const url = "http://127.0.0.1:18789/";
 
     if (!isTauriRuntime()) {
@@ -217,7 +236,7 @@ export const openclawBridge: OpenClawBridge = {
       }
     }
 
-    return invoke<OfficialWebStatus>("ensure_official_web_ready");
+    return invoke<OfficialWebStatus>("ensure_official_web_ready", { timeoutMs });
   },
 
   async openOfficialWebWindow() {
@@ -298,11 +317,29 @@ export const openclawBridge: OpenClawBridge = {
     return invoke<BrowserRelayDiagnostic>("diagnose_browser_relay");
   },
 
-  async saveApiKey(providerId: string, apiKey: string) {
+  async saveApiKey(providerId: string, apiKey: string, agentId?: string) {
     if (!isTauriRuntime()) {
-      return { ok: providerId.trim().length > 0 && apiKey.trim().length > 0 };
+      return {
+        ok: providerId.trim().length > 0 && apiKey.trim().length > 0,
+        profileId: `${providerId.trim().toLowerCase()}:default`,
+        modelSwitched: false
+      } satisfies ApiKeySaveResult;
     }
-    return invoke<{ ok: boolean }>("save_api_key", { providerId, apiKey });
+    return invoke<ApiKeySaveResult>("save_api_key", { providerId, apiKey, agentId });
+  },
+
+  async validateApiKey(providerId: string, apiKey: string, baseUrl?: string) {
+    if (!isTauriRuntime()) {
+      return {
+        providerId,
+        endpoint: baseUrl ?? "",
+        valid: false,
+        models: [],
+        errorKind: "network",
+        error: "Native runtime required"
+      } satisfies ApiKeyValidationStatus;
+    }
+    return invoke<ApiKeyValidationStatus>("validate_api_key", { providerId, apiKey, baseUrl });
   },
 
   async detectLocalCodexAuth() {
@@ -317,14 +354,14 @@ export const openclawBridge: OpenClawBridge = {
     return invoke<CodexAuthStatus>("detect_local_codex_auth");
   },
 
-  async reuseLocalCodexAuth(setDefaultModel = true) {
+  async reuseLocalCodexAuth(setDefaultModel = true, agentId?: string) {
     if (!isTauriRuntime()) {
       return {
         reused: false,
         message: "Native runtime required"
-      } satisfies LocalCodexReuseResult;
+      } satisfies LocalAuthReuseResult;
     }
-    return invoke<LocalCodexReuseResult>("reuse_local_codex_auth", { setDefaultModel });
+    return invoke<LocalAuthReuseResult>("reuse_local_codex_auth", { setDefaultModel, agentId });
   },
 
   async validateLocalCodexConnectivity() {
@@ -338,5 +375,243 @@ export const openclawBridge: OpenClawBridge = {
     }
 
     return invoke<CodexConnectivityStatus>("validate_local_codex_connectivity");
+  },
+
+  async listConfigBackups() {
+    if (!isTauriRuntime()) {
+      return [];
+    }
+    return invoke<ConfigBackupEntry[]>("list_config_backups");
+  },
+
+  async diffConfigBackup(id: string) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<ConfigBackupDiff>("diff_config_backup", { id });
+  },
+
+  async restoreConfigBackup(id: string) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<ConfigBackupEntry>("restore_config_backup", { id });
+  },
+
+  async getOpenClawConfigStatus() {
+    if (!isTauriRuntime()) {
+      return {
+        path: "~/.openclaw/openclaw.json",
+        state: "missing" as const
+      };
+    }
+    return invoke<OpenClawConfigStatus>("get_openclaw_config_status");
+  },
+
+  async validateOpenClawConfig() {
+    if (!isTauriRuntime()) {
+      return {
+        path: "~/.openclaw/openclaw.json",
+        valid: true,
+        diagnostics: []
+      };
+    }
+    return invoke<ConfigValidationReport>("validate_openclaw_config");
+  },
+
+  async getGatewaySupervisorStatus() {
+    if (!isTauriRuntime()) {
+      return {
+        running: false,
+        supervised: false,
+        crashStreak: 0,
+        totalRestarts: 0,
+        gaveUp: false,
+        exits: []
+      };
+    }
+    return invoke<GatewaySupervisorStatus>("get_gateway_supervisor_status");
+  },
+
+  async tailGatewayLog(lines: number, follow: boolean) {
+    if (!isTauriRuntime()) {
+      return { path: "", lines: [], following: false };
+    }
+    return invoke<GatewayLogTail>("tail_gateway_log", { lines, follow });
+  },
+
+  async stopGateway() {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<GatewayStopResult>("stop_gateway");
+  },
+
+  async restartGateway() {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<GatewaySupervisorStatus>("restart_gateway");
+  },
+
+  async getGatewayEndpoint() {
+    if (!isTauriRuntime()) {
+      return { host: "127.0.0.1", port: 18789, source: "default" } satisfies GatewayEndpoint;
+    }
+    return invoke<GatewayEndpoint>("get_gateway_endpoint");
+  },
+
+  async setGatewayPort(port: number) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<GatewayEndpoint>("set_gateway_port", { port });
+  },
+
+  async getGatewayHealth() {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<GatewayHealthProbe>("get_gateway_health");
+  },
+
+  async cancelOfficialWebWait(waitId?: number) {
+    if (!isTauriRuntime()) {
+      return;
+    }
+    await invoke("cancel_official_web_wait", { waitId });
+  },
+
+  async listProfiles() {
+    if (!isTauriRuntime()) {
+      return { activeId: "default", profiles: [] };
+    }
+    return invoke<ProfileList>("list_profiles");
+  },
+
+  async createProfile(name: string, stateDir?: string, port?: number) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<ProfileList>("create_profile", { name, stateDir, port });
+  },
+
+  async switchProfile(id: string) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<ProfileList>("switch_profile", { id });
+  },
+
+  async listOpenClawAgents() {
+    if (!isTauriRuntime()) {
+      return [];
+    }
+    return invoke<OpenClawAgentSummary[]>("list_openclaw_agents");
+  },
+
+  async getAuthHealth() {
+    if (!isTauriRuntime()) {
+      return [];
+    }
+    return invoke<AuthProfileHealth[]>("get_auth_health");
+  },
+
+  async getCodexSyncStatus() {
+    if (!isTauriRuntime()) {
+      return {
+        enabled: false,
+        watching: false,
+        codexAuthPath: "",
+        auditPath: ""
+      } satisfies CodexSyncStatus;
+    }
+    return invoke<CodexSyncStatus>("get_codex_sync_status");
+  },
+
+  async setCodexSync(enabled: boolean, agentId?: string) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<CodexSyncStatus>("set_codex_sync", { enabled, agentId });
+  },
+
+  async listCodexSyncAudit(limit?: number) {
+    if (!isTauriRuntime()) {
+      return [];
+    }
+    return invoke<CodexSyncAuditEntry[]>("list_codex_sync_audit", { limit });
+  },
+
+  async reuseLocalClaudeAuth(setDefaultModel = true, agentId?: string) {
+    if (!isTauriRuntime()) {
+      return {
+        reused: false,
+        message: "Native runtime required"
+      } satisfies LocalAuthReuseResult;
+    }
+    return invoke<LocalAuthReuseResult>("reuse_local_claude_auth", { setDefaultModel, agentId });
+  },
+
+  async reuseLocalGeminiAuth(setDefaultModel = true, agentId?: string) {
+    if (!isTauriRuntime()) {
+      return {
+        reused: false,
+        message: "Native runtime required"
+      } satisfies LocalAuthReuseResult;
+    }
+    return invoke<LocalAuthReuseResult>("reuse_local_gemini_auth", { setDefaultModel, agentId });
+  },
+
+  async reuseLocalAuth(toolId: string, setDefaultModel = true, agentId?: string) {
+    if (!isTauriRuntime()) {
+      return {
+        reused: false,
+        message: "Native runtime required"
+      } satisfies LocalAuthReuseResult;
+    }
+    return invoke<LocalAuthReuseResult>("reuse_local_auth", { toolId, setDefaultModel, agentId });
+  },
+
+  async listAuthProfiles(agentId?: string) {
+    if (!isTauriRuntime()) {
+      return [];
+    }
+    return invoke<AuthProfileEntry[]>("list_auth_profiles", { agentId });
+  },
+
+  async deleteAuthProfile(profileId: string, agentId?: string) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<AuthProfileDeleteResult>("delete_auth_profile", { profileId, agentId });
+  },
+
+  async renameAuthProfile(profileId: string, newId: string) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<string>("rename_auth_profile", { profileId, newId });
+  },
+
+  async reorderAuthProfiles(providerId: string, profileIds: string[]) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<string[]>("reorder_auth_profiles", { providerId, profileIds });
+  },
+
+  async setPrimaryAuthProfile(profileId: string) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<string[]>("set_primary_auth_profile", { profileId });
+  },
+
+  async probeModel(providerId: string, model?: string) {
+    if (!isTauriRuntime()) {
+      throw new Error("Native runtime required");
+    }
+    return invoke<ModelProbeResult>("probe_model", { providerId, model });
   }
 };
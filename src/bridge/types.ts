@@ -28,14 +28,33 @@ export type CodexConnectivityStatus = {
   command: string;
 };
 
-export type LocalCodexReuseResult = {
+export type LocalAuthReuseResult = {
   reused: boolean;
   profileId?: string;
+  agentIds?: string[];
   model?: string;
   message: string;
   error?: string;
 };
 
+export type ApiKeySaveResult = {
+  ok: boolean;
+  profileId: string;
+  agentIds?: string[];
+  model?: string;
+  modelSwitched: boolean;
+};
+
+export type ApiKeyValidationStatus = {
+  providerId: string;
+  endpoint: string;
+  valid: boolean;
+  httpStatus?: number;
+  models: string[];
+  errorKind?: "invalid_key" | "quota" | "network" | "unexpected";
+  error?: string;
+};
+
 export type OAuthLoginResult = {
   providerId: string;
   launched: boolean;
@@ -50,12 +69,29 @@ export type OllamaStatus = {
   error?: string;
 };
 
+export type GatewayHealthState = "ready" | "starting" | "unhealthy" | "portInUse" | "down";
+
+export type GatewayHealthProbe = {
+  state: GatewayHealthState;
+  url: string;
+  statusCode?: number;
+  isOpenclaw: boolean;
+  tokenAccepted?: boolean;
+  latencyMs?: number;
+  version?: string;
+  error?: string;
+};
+
 export type OfficialWebStatus = {
   ready: boolean;
   installed: boolean;
   running: boolean;
   started: boolean;
   url: string;
+  port?: number;
+  suggestedPort?: number;
+  state?: GatewayHealthState;
+  health?: GatewayHealthProbe;
   commandHint: string;
   message: string;
   error?: string;
@@ -107,21 +143,256 @@ export type BrowserRelayDiagnostic = {
   commandHint: string;
 };
 
+export type ConfigBackupEntry = {
+  id: string;
+  kind: string;
+  sourcePath: string;
+  createdAtMs: number;
+  reason: string;
+  sizeBytes: number;
+};
+
+export type ConfigBackupDiff = {
+  backup: ConfigBackupEntry;
+  currentExists: boolean;
+  identical: boolean;
+  diff: string;
+};
+
+export type ConfigParseError = {
+  path: string;
+  message: string;
+  line?: number;
+  column?: number;
+};
+
+export type OpenClawConfigStatus = {
+  path: string;
+  state: "missing" | "ok" | "corrupt";
+  error?: ConfigParseError;
+};
+
+export type ConfigDiagnostic = {
+  severity: "error" | "warning" | "info";
+  pointer: string;
+  message: string;
+  hint?: string;
+};
+
+export type ConfigValidationReport = {
+  path: string;
+  valid: boolean;
+  diagnostics: ConfigDiagnostic[];
+};
+
+export type WatchedFileChange = {
+  path: string;
+  state: "missing" | "ok" | "corrupt";
+  changedSections: string[];
+};
+
+export type GatewayExitRecord = {
+  pid: number;
+  code?: number;
+  signal?: number;
+  startedAtMs: number;
+  exitedAtMs: number;
+  uptimeMs: number;
+};
+
+export type GatewaySupervisorStatus = {
+  running: boolean;
+  pid?: number;
+  port?: number;
+  supervised: boolean;
+  crashStreak: number;
+  totalRestarts: number;
+  gaveUp: boolean;
+  startedAtMs?: number;
+  nextRestartAtMs?: number;
+  exits: GatewayExitRecord[];
+};
+
+export type GatewayLogLine = {
+  stream: string;
+  line: string;
+};
+
+export type GatewayLogTail = {
+  path: string;
+  lines: string[];
+  following: boolean;
+};
+
+export type GatewayStopResult = {
+  stopped: boolean;
+  pid?: number;
+  forced: boolean;
+  status: GatewaySupervisorStatus;
+};
+
+export type GatewayEndpoint = {
+  host: string;
+  port: number;
+  source: "env" | "config" | "default" | "profile";
+  bind?: string;
+};
+
+export type OfficialWebWaitProgress = {
+  waitId: number;
+  attempt: number;
+  elapsedMs: number;
+  deadlineMs: number;
+  state: GatewayHealthState;
+};
+
+export type ProfileSummary = {
+  id: string;
+  name: string;
+  stateDir: string;
+  port: number;
+  active: boolean;
+  builtin: boolean;
+};
+
+export type ProfileList = {
+  activeId: string;
+  profiles: ProfileSummary[];
+};
+
+export type AgentAuthProfileSummary = {
+  id: string;
+  provider?: string;
+  kind?: string;
+  email?: string;
+  expiresAtMs?: number;
+};
+
+export type OpenClawAgentSummary = {
+  id: string;
+  agentDir: string;
+  authProfilesPath: string;
+  isDefault: boolean;
+  profiles: AgentAuthProfileSummary[];
+  error?: string;
+};
+
+export type AuthExpiryState = "valid" | "expiring" | "expired" | "refreshPending" | "noExpiry";
+
+export type AuthProfileHealth = AgentAuthProfileSummary & {
+  agentId: string;
+  expiresInMs?: number;
+  state: AuthExpiryState;
+  refreshable: boolean;
+};
+
+export type CodexSyncAuditEntry = {
+  atMs: number;
+  trigger: "startup" | "profile-switch" | "enable" | "file-change" | "openclaw-change";
+  outcome: "synced" | "skipped" | "failed";
+  profileId?: string;
+  agentIds: string[];
+  skippedAgentIds: string[];
+  writtenBackFrom?: string;
+  lastRefresh?: string;
+  codexExpiresAtMs?: number;
+  message: string;
+};
+
+export type CodexSyncStatus = {
+  enabled: boolean;
+  agentId?: string;
+  watching: boolean;
+  codexAuthPath: string;
+  auditPath: string;
+  lastEntry?: CodexSyncAuditEntry;
+};
+
+export type AuthProfileEntry = AgentAuthProfileSummary & {
+  agentId: string;
+  mode?: string;
+  secretFields: string[];
+  inAuthProfiles: boolean;
+  inConfig: boolean;
+  orderIndex?: number;
+  primary: boolean;
+};
+
+export type AuthProfileDeleteResult = {
+  profileId: string;
+  agentIds: string[];
+  configCleaned: boolean;
+};
+
+export type ModelProbeUsage = {
+  input?: number;
+  output?: number;
+  total?: number;
+};
+
+export type ModelProbeResult = {
+  ok: boolean;
+  providerId: string;
+  model: string;
+  via: "gateway" | "local";
+  authProfileId?: string;
+  expectedAuthProfileId?: string;
+  expected: string;
+  response?: string;
+  answeredBy?: string;
+  answeredByProvider?: boolean;
+  latencyMs: number;
+  usage?: ModelProbeUsage;
+  error?: string;
+  command: string;
+};
+
 export type OpenClawBridge = {
   listOAuthProviders: () => Promise<OAuthProvider[]>;
   detectLocalOAuthTools: () => Promise<LocalOAuthToolStatus[]>;
   startOAuthLogin: (providerId: string) => Promise<OAuthLoginResult>;
   checkOllama: () => Promise<OllamaStatus>;
   bootstrapOpenClaw: () => Promise<BootstrapStatus>;
-  ensureOfficialWebReady: () => Promise<OfficialWebStatus>;
+  ensureOfficialWebReady: (timeoutMs?: number) => Promise<OfficialWebStatus>;
   openOfficialWebWindow: () => Promise<OpenOfficialWebResult>;
   getBrowserModeStatus: () => Promise<BrowserModeStatus>;
   setBrowserMode: (mode: string) => Promise<BrowserModeStatus>;
   getBrowserRelayStatus: () => Promise<BrowserRelayStatus>;
   prepareBrowserRelay: () => Promise<BrowserRelayStatus>;
   diagnoseBrowserRelay: () => Promise<BrowserRelayDiagnostic>;
-  saveApiKey: (providerId: string, apiKey: string) => Promise<{ ok: boolean }>;
+  saveApiKey: (providerId: string, apiKey: string, agentId?: string) => Promise<ApiKeySaveResult>;
+  validateApiKey: (providerId: string, apiKey: string, baseUrl?: string) => Promise<ApiKeyValidationStatus>;
   detectLocalCodexAuth: () => Promise<CodexAuthStatus>;
-  reuseLocalCodexAuth: (setDefaultModel?: boolean) => Promise<LocalCodexReuseResult>;
+  reuseLocalCodexAuth: (setDefaultModel?: boolean, agentId?: string) => Promise<LocalAuthReuseResult>;
   validateLocalCodexConnectivity: () => Promise<CodexConnectivityStatus>;
+  listConfigBackups: () => Promise<ConfigBackupEntry[]>;
+  diffConfigBackup: (id: string) => Promise<ConfigBackupDiff>;
+  restoreConfigBackup: (id: string) => Promise<ConfigBackupEntry>;
+  getOpenClawConfigStatus: () => Promise<OpenClawConfigStatus>;
+  validateOpenClawConfig: () => Promise<ConfigValidationReport>;
+  getGatewaySupervisorStatus: () => Promise<GatewaySupervisorStatus>;
+  tailGatewayLog: (lines: number, follow: boolean) => Promise<GatewayLogTail>;
+  stopGateway: () => Promise<GatewayStopResult>;
+  restartGateway: () => Promise<GatewaySupervisorStatus>;
+  getGatewayEndpoint: () => Promise<GatewayEndpoint>;
+  setGatewayPort: (port: number) => Promise<GatewayEndpoint>;
+  getGatewayHealth: () => Promise<GatewayHealthProbe>;
+  cancelOfficialWebWait: (waitId?: number) => Promise<void>;
+  listProfiles: () => Promise<ProfileList>;
+  createProfile: (name: string, stateDir?: string, port?: number) => Promise<ProfileList>;
+  switchProfile: (id: string) => Promise<ProfileList>;
+  listOpenClawAgents: () => Promise<OpenClawAgentSummary[]>;
+  getAuthHealth: () => Promise<AuthProfileHealth[]>;
+  getCodexSyncStatus: () => Promise<CodexSyncStatus>;
+  setCodexSync: (enabled: boolean, agentId?: string) => Promise<CodexSyncStatus>;
+  listCodexSyncAudit: (limit?: number) => Promise<CodexSyncAuditEntry[]>;
+  reuseLocalClaudeAuth: (setDefaultModel?: boolean, agentId?: string) => Promise<LocalAuthReuseResult>;
+  reuseLocalGeminiAuth: (setDefaultModel?: boolean, agentId?: string) => Promise<LocalAuthReuseResult>;
+  reuseLocalAuth: (toolId: string, setDefaultModel?: boolean, agentId?: string) => Promise<LocalAuthReuseResult>;
+  listAuthProfiles: (agentId?: string) => Promise<AuthProfileEntry[]>;
+  deleteAuthProfile: (profileId: string, agentId?: string) => Promise<AuthProfileDeleteResult>;
+  renameAuthProfile: (profileId: string, newId: string) => Promise<string>;
+  reorderAuthProfiles: (providerId: string, profileIds: string[]) => Promise<string[]>;
+  setPrimaryAuthProfile: (profileId: string) => Promise<string[]>;
+  probeModel: (providerId: string, model?: string) => Promise<ModelProbeResult>;
 };
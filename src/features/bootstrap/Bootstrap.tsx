@@ -2,6 +2,7 @@ import { useEffect, useState } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { useTranslation } from "react-i18next";
 import { openclawBridge } from "../../bridge/openclawBridge";
+import type { OfficialWebWaitProgress } from "../../bridge/types";
 
 type Props = {
   onStatus: (message: string) => void;
@@ -15,6 +16,7 @@ export default function Bootstrap({ onStatus, onReady }: Props) {
   const [logs, setLogs] = useState<string[]>([]);
   const [liveLogs, setLiveLogs] = useState<string[]>([]);
   const [elapsedSec, setElapsedSec] = useState(0);
+  const [webWait, setWebWait] = useState<OfficialWebWaitProgress | null>(null);
 
   const visibleLogs = running ? liveLogs : logs.length > 0 ? logs : liveLogs;
 
@@ -24,6 +26,7 @@ export default function Bootstrap({ onStatus, onReady }: Props) {
     setLogs([]);
     setLiveLogs([]);
     setElapsedSec(0);
+    setWebWait(null);
     onStatus(t("status.bootstrap.running"));
 
     try {
@@ -45,12 +48,13 @@ export default function Bootstrap({ onStatus, onReady }: Props) {
       onStatus(`${t("status.bootstrap.failed")}: ${message}`);
     } finally {
       setRunning(false);
+      setWebWait(null);
     }
   }
 
   useEffect(() => {
     let cancelled = false;
-    let unlisten: (() => void) | undefined;
+    const unlisteners: Array<() => void> = [];
 
     const hasTauriInternals =
       typeof window !== "undefined" &&
@@ -67,7 +71,16 @@ export default function Bootstrap({ onStatus, onReady }: Props) {
           fn();
           return;
         }
-        unlisten = fn;
+        unlisteners.push(fn);
+      });
+      void listen<OfficialWebWaitProgress>("official-web-progress", (event) => {
+        setWebWait(event.payload);
+      }).then((fn) => {
+        if (cancelled) {
+          fn();
+          return;
+        }
+        unlisteners.push(fn);
       });
     }
 
@@ -75,9 +88,7 @@ export default function Bootstrap({ onStatus, onReady }: Props) {
 
     return () => {
       cancelled = true;
-      if (unlisten) {
-        unlisten();
-      }
+      unlisteners.forEach((fn) => fn());
     };
   }, []);
 
@@ -106,6 +117,19 @@ export default function Bootstrap({ onStatus, onReady }: Props) {
             <div className="bootstrap-progress-bar" />
           </div>
           <p className="hint">{t("bootstrap.runningDetail", { seconds: elapsedSec })}</p>
+          {webWait ? (
+            <div className="action-row">
+              <p className="hint">
+                {t("bootstrap.webWait", {
+                  attempt: webWait.attempt,
+                  seconds: Math.max(0, Math.ceil((webWait.deadlineMs - webWait.elapsedMs) / 1000))
+                })}
+              </p>
+              <button type="button" onClick={() => void openclawBridge.cancelOfficialWebWait(webWait.waitId)}>
+                {t("bootstrap.webWaitCancel")}
+              </button>
+            </div>
+          ) : null}
         </>
       ) : null}
       {error ? <div className="status-chip warn">{error}</div> : null}
@@ -5,6 +5,7 @@ import type {
   CodexAuthStatus,
   CodexConnectivityStatus,
   LocalOAuthToolStatus,
+  ModelProbeResult,
   OAuthProvider,
   OllamaStatus
 } from "../../bridge/types";
@@ -49,6 +50,7 @@ export default function Onboarding({ onStatus, onLoginSuccess }: Props) {
     defaultCodexConnectivityStatus
   );
   const [localOAuthTools, setLocalOAuthTools] = useState<LocalOAuthToolStatus[]>(defaultLocalOAuthTools);
+  const [modelProbe, setModelProbe] = useState<ModelProbeResult | null>(null);
   const [codexLoading, setCodexLoading] = useState(false);
   const [busy, setBusy] = useState(false);
 
@@ -144,6 +146,26 @@ export default function Onboarding({ onStatus, onLoginSuccess }: Props) {
       return;
     }
 
+    const localTool = localOAuthTools.find((tool) => tool.providerId === providerId && tool.authDetected);
+    if (localTool) {
+      setBusy(true);
+      onStatus(t("status.loading"));
+      try {
+        const result = await openclawBridge.reuseLocalAuth(localTool.id, true);
+        if (result.reused) {
+          onStatus(t("status.oauth.local.reused", { tool: localTool.label }));
+          onLoginSuccess();
+        } else {
+          onStatus(`${t("status.error")}: ${result.error ?? result.message}`);
+        }
+      } catch (error) {
+        onStatus(`${t("status.error")}: ${error instanceof Error ? error.message : String(error)}`);
+      } finally {
+        setBusy(false);
+      }
+      return;
+    }
+
     setBusy(true);
     onStatus(t("status.loading"));
     try {
@@ -188,6 +210,24 @@ export default function Onboarding({ onStatus, onLoginSuccess }: Props) {
     }
   }
 
+  async function probeSelectedProvider() {
+    if (!selectedProvider) {
+      return;
+    }
+    setBusy(true);
+    onStatus(t("status.loading"));
+    try {
+      const result = await openclawBridge.probeModel(selectedProvider);
+      setModelProbe(result);
+      onStatus(result.ok ? t("oauth.probe.ok") : `${t("oauth.probe.fail")}: ${result.error ?? "-"}`);
+    } catch (error) {
+      setModelProbe(null);
+      onStatus(`${t("status.error")}: ${error instanceof Error ? error.message : String(error)}`);
+    } finally {
+      setBusy(false);
+    }
+  }
+
   async function handleApiKeySave() {
     if (!apiProvider.trim() || !apiKey.trim()) {
       return;
@@ -253,6 +293,22 @@ export default function Onboarding({ onStatus, onLoginSuccess }: Props) {
             {codexConnectivityStatus.ok ? t("oauth.codex.validate.ok") : t("oauth.codex.validate.fail")}
             {codexConnectivityStatus.response ? `: ${codexConnectivityStatus.response}` : ""}
           </div>
+          {modelProbe ? (
+            <div className={modelProbe.ok ? "status-chip success" : "status-chip warn"}>
+              {modelProbe.ok ? t("oauth.probe.ok") : t("oauth.probe.fail")}:{" "}
+              {t("oauth.probe.detail", {
+                model: modelProbe.answeredBy ?? modelProbe.model,
+                profile:
+                  modelProbe.authProfileId ??
+                  (modelProbe.expectedAuthProfileId
+                    ? t("oauth.probe.expectedProfile", { profile: modelProbe.expectedAuthProfileId })
+                    : "-"),
+                latency: modelProbe.latencyMs,
+                tokens: modelProbe.usage?.total ?? "-"
+              })}
+              {modelProbe.ok && modelProbe.answeredByProvider == null ? ` (${t("oauth.probe.providerUnknown")})` : ""}
+            </div>
+          ) : null}
           <div className="local-oauth-tools">
             <strong>{t("oauth.local.title")}</strong>
             <ul>
@@ -316,6 +372,9 @@ export default function Onboarding({ onStatus, onLoginSuccess }: Props) {
             <button type="button" onClick={() => void validateCodexConnectivity()} disabled={busy || !codexAuthStatus.detected}>
               {t("oauth.codex.validate")}
             </button>
+            <button type="button" onClick={() => void probeSelectedProvider()} disabled={busy || !selectedProvider}>
+              {t("oauth.probe")}
+            </button>
             <button type="button" className="primary" onClick={() => void handleOAuthStart()} disabled={busy || !selectedProvider}>
               {t("oauth.start")}
             </button>
@@ -1,7 +1,16 @@
 import { useEffect, useState } from "react";
 import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
 import { openclawBridge } from "../../bridge/openclawBridge";
-import type { BrowserModeStatus, BrowserRelayDiagnostic, BrowserRelayStatus } from "../../bridge/types";
+import type {
+  AuthProfileEntry,
+  AuthProfileHealth,
+  BrowserModeStatus,
+  BrowserRelayDiagnostic,
+  BrowserRelayStatus,
+  OpenClawConfigStatus,
+  WatchedFileChange
+} from "../../bridge/types";
 import feedbackGroupQr from "../../assets/feedback-group-qr.png";
 
 type Props = {
@@ -21,11 +30,16 @@ export default function Shell({ onStatus, onBack }: Props) {
   const [officialLoading, setOfficialLoading] = useState(false);
   const [officialOpening, setOfficialOpening] = useState(false);
   const [officialError, setOfficialError] = useState("");
+  const [suggestedPort, setSuggestedPort] = useState<number | null>(null);
   const [browserMode, setBrowserMode] = useState<BrowserModeStatus | null>(null);
   const [selectedMode, setSelectedMode] = useState<"openclaw" | "chrome">("openclaw");
   const [settingsLoading, setSettingsLoading] = useState(false);
   const [settingsSaving, setSettingsSaving] = useState(false);
   const [settingsError, setSettingsError] = useState("");
+  const [configStatus, setConfigStatus] = useState<OpenClawConfigStatus | null>(null);
+  const [authAlerts, setAuthAlerts] = useState<AuthProfileHealth[]>([]);
+  const [authProfiles, setAuthProfiles] = useState<AuthProfileEntry[]>([]);
+  const [authProfilesBusy, setAuthProfilesBusy] = useState(false);
   const [relayStatus, setRelayStatus] = useState<BrowserRelayStatus | null>(null);
   const [relayLoading, setRelayLoading] = useState(false);
   const [relayPreparing, setRelayPreparing] = useState(false);
@@ -42,6 +56,7 @@ export default function Shell({ onStatus, onBack }: Props) {
       const result = await openclawBridge.ensureOfficialWebReady();
       setOfficialWebUrl(result.url || officialWebFallbackUrl);
       setOfficialReady(result.ready);
+      setSuggestedPort(result.suggestedPort ?? null);
       if (result.ready) {
         onStatus(t("status.shell.official"));
         return true;
@@ -62,6 +77,20 @@ export default function Shell({ onStatus, onBack }: Props) {
     }
   }
 
+  async function switchGatewayPort(port: number) {
+    setOfficialLoading(true);
+    try {
+      await openclawBridge.setGatewayPort(port);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      setOfficialError(message);
+      onStatus(`${t("status.error")}: ${message}`);
+      setOfficialLoading(false);
+      return;
+    }
+    await ensureOfficialWebReady();
+  }
+
   async function openOfficialWebWindow() {
     setOfficialOpening(true);
     setOfficialError("");
@@ -95,8 +124,12 @@ export default function Shell({ onStatus, onBack }: Props) {
     setSettingsLoading(true);
     setSettingsError("");
     try {
-      const result = await openclawBridge.getBrowserModeStatus();
+      const [result, config] = await Promise.all([
+        openclawBridge.getBrowserModeStatus(),
+        openclawBridge.getOpenClawConfigStatus()
+      ]);
       setBrowserMode(result);
+      setConfigStatus(config);
       setSelectedMode(result.mode === "chrome" ? "chrome" : "openclaw");
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error);
@@ -119,11 +152,50 @@ export default function Shell({ onStatus, onBack }: Props) {
       const message = error instanceof Error ? error.message : String(error);
       setSettingsError(message);
       onStatus(`${t("status.error")}: ${message}`);
+      setConfigStatus(await openclawBridge.getOpenClawConfigStatus().catch(() => null));
     } finally {
       setSettingsSaving(false);
     }
   }
 
+  async function loadAuthProfiles() {
+    try {
+      setAuthProfiles(await openclawBridge.listAuthProfiles());
+    } catch (error) {
+      setSettingsError(error instanceof Error ? error.message : String(error));
+    }
+  }
+
+  async function runAuthProfileAction(action: () => Promise<unknown>, doneMessage: string) {
+    setAuthProfilesBusy(true);
+    setSettingsError("");
+    try {
+      await action();
+      onStatus(doneMessage);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      setSettingsError(message);
+      onStatus(`${t("status.error")}: ${message}`);
+    } finally {
+      await loadAuthProfiles();
+      setAuthProfilesBusy(false);
+    }
+  }
+
+  function makeAuthProfilePrimary(profile: AuthProfileEntry) {
+    void runAuthProfileAction(
+      () => openclawBridge.setPrimaryAuthProfile(profile.id),
+      t("status.shell.authProfile.primary", { profile: profile.id })
+    );
+  }
+
+  function deleteAuthProfile(profile: AuthProfileEntry) {
+    void runAuthProfileAction(
+      () => openclawBridge.deleteAuthProfile(profile.id, profile.agentId),
+      t("status.shell.authProfile.deleted", { profile: profile.id })
+    );
+  }
+
   async function loadRelayStatus() {
     setRelayLoading(true);
     setRelayError("");
@@ -204,9 +276,80 @@ export default function Shell({ onStatus, onBack }: Props) {
     switchToHelpTab();
     void ensureOfficialWebReady();
     void loadBrowserModeStatus();
+    void loadAuthProfiles();
     void loadRelayStatus();
   }, []);
 
+  function trackAuthAlert(health: AuthProfileHealth) {
+    setAuthAlerts((prev) => [
+      ...prev.filter((item) => item.agentId !== health.agentId || item.id !== health.id),
+      health
+    ]);
+  }
+
+  useEffect(() => {
+    let cancelled = false;
+    const unlisteners: Array<() => void> = [];
+
+    const hasTauriInternals =
+      typeof window !== "undefined" &&
+      typeof (window as Window & { __TAURI_INTERNALS__?: unknown }).__TAURI_INTERNALS__ !== "undefined";
+
+    const subscribe = (promise: Promise<() => void>) => {
+      void promise.then((fn) => {
+        if (cancelled) {
+          fn();
+          return;
+        }
+        unlisteners.push(fn);
+      });
+    };
+
+    const refreshAuthHealth = () => {
+      void openclawBridge.getAuthHealth().then((items) => {
+        if (!cancelled) {
+          setAuthAlerts(items.filter((item) => item.state === "expiring" || item.state === "expired"));
+        }
+      });
+    };
+
+    if (hasTauriInternals) {
+      // openclaw.json was edited outside the app (e.g. by the CLI); refresh instead of polling.
+      subscribe(
+        listen<WatchedFileChange>("config-changed", (event) => {
+          const { state, changedSections } = event.payload;
+          if (state !== "ok" || changedSections.includes("browser")) {
+            void loadBrowserModeStatus();
+          } else {
+            void openclawBridge
+              .getOpenClawConfigStatus()
+              .then((config) => {
+                if (!cancelled) {
+                  setConfigStatus(config);
+                }
+              })
+              .catch(() => undefined);
+          }
+        })
+      );
+      subscribe(
+        listen<WatchedFileChange>("auth-profiles-changed", () => {
+          void loadAuthProfiles();
+          refreshAuthHealth();
+        })
+      );
+      for (const name of ["auth-expiring", "auth-expired"]) {
+        subscribe(listen<AuthProfileHealth>(name, (event) => trackAuthAlert(event.payload)));
+      }
+      refreshAuthHealth();
+    }
+
+    return () => {
+      cancelled = true;
+      unlisteners.forEach((fn) => fn());
+    };
+  }, []);
+
   return (
     <section className="shell-root">
       <div className="shell-nav">
@@ -352,6 +495,15 @@ export default function Shell({ onStatus, onBack }: Props) {
               <button type="button" onClick={() => void ensureOfficialWebReady()} disabled={officialLoading || officialOpening}>
                 {t("shell.official.retry")}
               </button>
+              {suggestedPort ? (
+                <button
+                  type="button"
+                  onClick={() => void switchGatewayPort(suggestedPort)}
+                  disabled={officialLoading || officialOpening}
+                >
+                  {t("shell.official.usePort", { port: suggestedPort })}
+                </button>
+              ) : null}
             </div>
           </div>
         ) : (
@@ -361,6 +513,24 @@ export default function Shell({ onStatus, onBack }: Props) {
             <p className="hint">{t("shell.settings.relayHint")}</p>
             {settingsLoading ? <div className="status-chip">{t("status.loading")}</div> : null}
             {settingsError ? <div className="status-chip warn">{settingsError}</div> : null}
+            {configStatus?.state === "corrupt" ? (
+              <div className="status-chip warn">
+                {t("shell.settings.configCorrupt", {
+                  path: configStatus.path,
+                  line: configStatus.error?.line ?? "?",
+                  column: configStatus.error?.column ?? "?",
+                  message: configStatus.error?.message ?? ""
+                })}
+              </div>
+            ) : null}
+            {authAlerts.map((alert) => (
+              <div key={`${alert.agentId}/${alert.id}`} className="status-chip warn">
+                {t(alert.state === "expired" ? "shell.settings.authExpired" : "shell.settings.authExpiring", {
+                  profile: alert.email ?? alert.id,
+                  agent: alert.agentId
+                })}
+              </div>
+            ))}
 
             <div className="shell-mode-grid">
               <label className={`shell-mode-card ${selectedMode === "openclaw" ? "selected" : ""}`}>
@@ -425,6 +595,31 @@ export default function Shell({ onStatus, onBack }: Props) {
                 {t("shell.settings.refresh")}
               </button>
             </div>
+
+            <div className="detected-list">
+              <strong>{t("shell.settings.authProfiles")}</strong>
+              {authProfiles.length ? (
+                <ul>
+                  {authProfiles.map((profile) => (
+                    <li key={`${profile.agentId}/${profile.id}`}>
+                      <code>{profile.id}</code> {profile.email ? `(${profile.email}) ` : ""}
+                      {profile.primary ? <span className="status-chip success">{t("shell.settings.authProfiles.primary")}</span> : null}
+                      {!profile.inAuthProfiles ? <span className="status-chip warn">{t("shell.settings.authProfiles.orphan")}</span> : null}
+                      {profile.primary || !profile.provider ? null : (
+                        <button type="button" onClick={() => makeAuthProfilePrimary(profile)} disabled={authProfilesBusy}>
+                          {t("shell.settings.authProfiles.makePrimary")}
+                        </button>
+                      )}
+                      <button type="button" onClick={() => deleteAuthProfile(profile)} disabled={authProfilesBusy}>
+                        {t("shell.settings.authProfiles.delete")}
+                      </button>
+                    </li>
+                  ))}
+                </ul>
+              ) : (
+                <p className="hint">{t("shell.settings.authProfiles.none")}</p>
+              )}
+            </div>
           </div>
         )}
       </div>
@@ -20,6 +20,12 @@ export const resources = {
       "oauth.codex.validate": "验证 Codex 通信",
       "oauth.codex.validate.ok": "Codex 通信验证成功",
       "oauth.codex.validate.fail": "Codex 通信验证失败",
+      "oauth.probe": "经 OpenClaw 测试模型",
+      "oauth.probe.ok": "OpenClaw 模型测试成功",
+      "oauth.probe.fail": "OpenClaw 模型测试失败",
+      "oauth.probe.detail": "{{model}}，凭据 {{profile}}，耗时 {{latency}} ms，{{tokens}} tokens",
+      "oauth.probe.expectedProfile": "{{profile}}（预期）",
+      "oauth.probe.providerUnknown": "OpenClaw 未报告实际应答的提供方",
       "oauth.local.title": "本机可复用登录能力",
       "oauth.local.ready": "已检测到可复用凭据",
       "oauth.local.cliOnly": "已安装 CLI（尚未确认登录）",
@@ -29,7 +35,7 @@ export const resources = {
       "apikey.provider": "Provider ID",
       "apikey.key": "API Key",
       "apikey.save": "保存 API Key",
-      "apikey.hint": "当前为骨架版本，下一步接入系统安全存储。",
+      "apikey.hint": "API Key 会写入 OpenClaw auth-profiles，并登记到 openclaw.json。",
       "ollama.endpoint": "本地端点",
       "ollama.check": "检测 Ollama",
       "ollama.ok": "已连接",
@@ -40,8 +46,9 @@ export const resources = {
       "status.error": "发生错误",
       "status.oauth.start": "已触发 OAuth 登录流程",
       "status.oauth.codex.reused": "已复用本机 Codex 登录状态，无需再次登录",
+      "status.oauth.local.reused": "已导入本机 {{tool}} 登录状态，无需再次登录",
       "status.oauth.local.reused": "已复用本机 {{provider}} 登录状态，无需再次登录",
-      "status.apikey.saved": "API Key 已保存到系统安全存储（占位）",
+      "status.apikey.saved": "API Key 已保存到 OpenClaw 认证配置",
       "status.shell.entered": "已进入登录后的 Shell 页面",
       "status.bootstrap.running": "正在自动安装并初始化 OpenClaw",
       "status.bootstrap.ready": "OpenClaw 安装与初始化完成",
@@ -101,6 +108,17 @@ export const resources = {
       "shell.settings.title": "浏览器模式设置",
       "shell.settings.desc": "默认会优先使用托管隔离浏览器。你可以在这里切换为 Chrome Profile 模式。",
       "shell.settings.relayHint": "提示：如果使用 Chrome Profile 模式，请先在“帮助”页完成中继扩展准备与启用。",
+      "shell.settings.configCorrupt": "配置文件已损坏，修复前不会写入：{{path}}（第 {{line}} 行，第 {{column}} 列）{{message}}",
+      "shell.settings.authExpiring": "{{profile}}（智能体 {{agent}}）的登录即将过期",
+      "shell.settings.authExpired": "{{profile}}（智能体 {{agent}}）的登录已过期，请重新登录",
+      "shell.settings.authProfiles": "登录凭据",
+      "shell.settings.authProfiles.none": "暂无登录凭据",
+      "shell.settings.authProfiles.primary": "首选",
+      "shell.settings.authProfiles.orphan": "仅存在于配置中",
+      "shell.settings.authProfiles.makePrimary": "设为首选",
+      "shell.settings.authProfiles.delete": "删除",
+      "status.shell.authProfile.primary": "已将 {{profile}} 设为首选登录凭据",
+      "status.shell.authProfile.deleted": "已删除登录凭据 {{profile}}",
       "shell.settings.mode.current": "当前模式",
       "shell.settings.mode.openclaw": "托管隔离浏览器（openclaw）",
       "shell.settings.mode.openclaw.desc": "推荐。独立浏览器配置，不污染个人浏览器环境。",
@@ -122,11 +140,14 @@ export const resources = {
       "shell.official.switchHint": "随时切回主页面：点击 Dock/任务栏中的 openclaw-desktop 主窗口。",
       "shell.official.open": "打开官方本地页窗口",
       "shell.official.retry": "重试连接",
+      "shell.official.usePort": "改用端口 {{port}}",
       "bootstrap.title": "正在准备 OpenClaw 运行环境",
       "bootstrap.desc": "首次启动会自动安装 OpenClaw、初始化配置并拉起官方本地服务。",
       "bootstrap.running": "初始化进行中...",
       "bootstrap.runningDetail": "正在执行自动安装与初始化（已用 {{seconds}} 秒）",
       "bootstrap.waitingLogs": "正在执行步骤，请稍候，日志会实时刷新。",
+      "bootstrap.webWait": "正在等待本地网关就绪（第 {{attempt}} 次检测，剩余 {{seconds}} 秒）",
+      "bootstrap.webWaitCancel": "停止等待",
       "bootstrap.retry": "重新初始化",
       "bootstrap.logs": "执行日志",
       "update.check": "检测更新",
@@ -162,6 +183,12 @@ export const resources = {
       "oauth.codex.validate": "Validate Codex Connectivity",
       "oauth.codex.validate.ok": "Codex connectivity check passed",
       "oauth.codex.validate.fail": "Codex connectivity check failed",
+      "oauth.probe": "Test Model via OpenClaw",
+      "oauth.probe.ok": "OpenClaw model check passed",
+      "oauth.probe.fail": "OpenClaw model check failed",
+      "oauth.probe.detail": "{{model}}, profile {{profile}}, {{latency}} ms, {{tokens}} tokens",
+      "oauth.probe.expectedProfile": "{{profile}} (expected)",
+      "oauth.probe.providerUnknown": "OpenClaw did not report which provider answered",
       "oauth.local.title": "Reusable local logins",
       "oauth.local.ready": "Reusable credential detected",
       "oauth.local.cliOnly": "CLI found (auth not confirmed)",
@@ -171,7 +198,7 @@ export const resources = {
       "apikey.provider": "Provider ID",
       "apikey.key": "API Key",
       "apikey.save": "Save API Key",
-      "apikey.hint": "Scaffold mode: secure keychain integration is next.",
+      "apikey.hint": "The key is written to OpenClaw auth-profiles and registered in openclaw.json.",
       "ollama.endpoint": "Local endpoint",
       "ollama.check": "Check Ollama",
       "ollama.ok": "Connected",
@@ -182,8 +209,9 @@ export const resources = {
       "status.error": "Something went wrong",
       "status.oauth.start": "OAuth flow triggered",
       "status.oauth.codex.reused": "Local Codex login reused, no extra login required",
+      "status.oauth.local.reused": "Local {{tool}} login imported, no extra login required",
       "status.oauth.local.reused": "Reused local {{provider}} login, no extra login required",
-      "status.apikey.saved": "API key saved to secure storage (placeholder)",
+      "status.apikey.saved": "API key saved to OpenClaw auth profiles",
       "status.shell.entered": "Entered post-login shell",
       "status.bootstrap.running": "Installing and initializing OpenClaw automatically",
       "status.bootstrap.ready": "OpenClaw install and initialization complete",
@@ -243,6 +271,17 @@ export const resources = {
       "shell.settings.title": "Browser Mode Settings",
       "shell.settings.desc": "Default is managed isolated browser. You can switch to Chrome profile mode here.",
       "shell.settings.relayHint": "Tip: if you choose Chrome Profile mode, finish relay extension setup in the Help tab first.",
+      "shell.settings.configCorrupt": "Config file is corrupt and will not be written until fixed: {{path}} (line {{line}}, column {{column}}) {{message}}",
+      "shell.settings.authExpiring": "Login for {{profile}} (agent {{agent}}) expires soon",
+      "shell.settings.authExpired": "Login for {{profile}} (agent {{agent}}) has expired; log in again",
+      "shell.settings.authProfiles": "Auth profiles",
+      "shell.settings.authProfiles.none": "No auth profiles yet",
+      "shell.settings.authProfiles.primary": "Primary",
+      "shell.settings.authProfiles.orphan": "Only in config",
+      "shell.settings.authProfiles.makePrimary": "Make primary",
+      "shell.settings.authProfiles.delete": "Delete",
+      "status.shell.authProfile.primary": "{{profile}} is now the primary auth profile",
+      "status.shell.authProfile.deleted": "Deleted auth profile {{profile}}",
       "shell.settings.mode.current": "Current mode",
       "shell.settings.mode.openclaw": "Managed Isolated Browser (openclaw)",
       "shell.settings.mode.openclaw.desc": "Recommended. Separate profile and cleaner isolation from your daily browser.",
@@ -264,11 +303,14 @@ export const resources = {
       "shell.official.switchHint": "To switch back anytime, focus the openclaw-desktop main window from Dock/taskbar.",
       "shell.official.open": "Open official local window",
       "shell.official.retry": "Retry connection",
+      "shell.official.usePort": "Use port {{port}}",
       "bootstrap.title": "Preparing OpenClaw runtime",
       "bootstrap.desc": "First launch auto-installs OpenClaw, initializes config, and starts official local services.",
       "bootstrap.running": "Bootstrap in progress...",
       "bootstrap.runningDetail": "Auto install/init in progress (elapsed {{seconds}}s)",
       "bootstrap.waitingLogs": "Running bootstrap steps, logs will appear here in real time.",
+      "bootstrap.webWait": "Waiting for the local gateway (check {{attempt}}, {{seconds}}s left)",
+      "bootstrap.webWaitCancel": "Stop waiting",
       "bootstrap.retry": "Retry bootstrap",
       "bootstrap.logs": "Execution logs",
       "update.check": "Check Updates",